@@ -0,0 +1,309 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::diff::diff_plans;
+use crate::timing::get_term_offset;
+use crate::{Courses, Credits, ScheduleOptions, Term, TermType, TracedTerm};
+
+/// How many plans with tighter unit limits than the options are tried for a
+/// more balanced load, each spreading the courses over one more term.
+const BALANCED_VARIANTS_COUNT: usize = 3;
+
+/// What alternative plans are ranked by first. Ties are broken by the other
+/// objectives in the order they are listed here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Objective {
+    FewestTerms,
+    MostBalanced,
+    FewestSummers,
+}
+
+/// How a plan measures up against each objective.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlanScore {
+    /// The terms from the start of the plan through its last term, counting
+    /// the terms without courses in between.
+    pub terms_count: usize,
+    /// The difference in units between the fullest and the emptiest term
+    /// with courses.
+    pub unit_spread: Credits,
+    pub summer_terms_count: usize,
+}
+
+impl PlanScore {
+    fn new(terms: &[Term], start_term: &TermType) -> PlanScore {
+        let terms_count: usize = terms
+            .last()
+            .map_or(0, |x| get_term_offset(start_term, &x.term_type, x.year) + 1);
+        let most_units: Credits = terms.iter().map(|x| x.units).max().unwrap_or(Credits::ZERO);
+        let fewest_units: Credits = terms.iter().map(|x| x.units).min().unwrap_or(Credits::ZERO);
+
+        PlanScore {
+            terms_count,
+            unit_spread: most_units.saturating_sub(fewest_units),
+            summer_terms_count: terms
+                .iter()
+                .filter(|x| x.term_type == TermType::Summer)
+                .count(),
+        }
+    }
+
+    fn get_rank(&self, objective: Objective) -> [u64; 3] {
+        let terms_count: u64 = self.terms_count as u64;
+        let unit_spread: u64 = self.unit_spread.hundredths() as u64;
+        let summer_terms_count: u64 = self.summer_terms_count as u64;
+
+        match objective {
+            Objective::FewestTerms => [terms_count, unit_spread, summer_terms_count],
+            Objective::MostBalanced => [unit_spread, terms_count, summer_terms_count],
+            Objective::FewestSummers => [summer_terms_count, terms_count, unit_spread],
+        }
+    }
+}
+
+impl fmt::Display for PlanScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} terms, {} units between the fullest and emptiest term, {} summer term{}",
+            self.terms_count,
+            self.unit_spread,
+            self.summer_terms_count,
+            if self.summer_terms_count == 1 {
+                ""
+            } else {
+                "s"
+            }
+        )
+    }
+}
+
+/// A course one plan takes in a different term than another.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CourseMove {
+    pub course: String,
+    pub from: TracedTerm,
+    pub to: TracedTerm,
+}
+
+/// One of the plans `Courses::sample_alternative_plans` finds, with its score.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlternativePlan {
+    pub terms: Vec<Term>,
+    pub score: PlanScore,
+}
+
+impl AlternativePlan {
+    /// Finds the courses the other plan takes in a different term than this
+    /// one, sorted by name. Courses taken more than once are compared
+    /// instance by instance.
+    pub fn diff(&self, other: &AlternativePlan) -> Vec<CourseMove> {
+        diff_plans(&self.terms, &other.terms).moved
+    }
+}
+
+/// Gets the term each course instance of the plan is in, telling plans that
+/// only list the courses of a term in another order apart from the rest.
+fn get_plan_key(terms: &[Term]) -> Vec<(String, TracedTerm)> {
+    let mut plan_key: Vec<(String, TracedTerm)> = terms
+        .iter()
+        .flat_map(|x| {
+            x.courses.iter().map(move |y| {
+                (
+                    y.0.clone(),
+                    TracedTerm {
+                        term: x.term_type.clone(),
+                        year: x.year,
+                    },
+                )
+            })
+        })
+        .collect();
+    plan_key.sort_by(|x, y| x.0.cmp(&y.0));
+
+    plan_key
+}
+
+impl Courses {
+    /// Gets the orders the scheduler is given the courses in to find other
+    /// plans, beginning with the courses sorted by name. The catalog's own
+    /// order is left out as it changes from run to run.
+    fn get_alternative_course_orders(&self) -> Vec<Vec<String>> {
+        let course_names: Vec<String> = self.get_course_names();
+
+        let mut by_dependents: Vec<(usize, String)> = course_names
+            .iter()
+            .map(|x| (self.get_dependents(x).len(), x.clone()))
+            .collect();
+        by_dependents.sort_by_key(|x| Reverse(x.0));
+
+        let mut by_credits: Vec<(Credits, String)> = course_names
+            .iter()
+            .map(|x| (self.master_list[x].credits, x.clone()))
+            .collect();
+        by_credits.sort_by_key(|x| Reverse(x.0));
+
+        vec![
+            course_names.clone(),
+            course_names.iter().rev().cloned().collect(),
+            by_dependents.iter().map(|x| x.1.clone()).collect(),
+            by_dependents.iter().rev().map(|x| x.1.clone()).collect(),
+            by_credits.iter().map(|x| x.1.clone()).collect(),
+            by_credits.iter().rev().map(|x| x.1.clone()).collect(),
+        ]
+    }
+
+    /// Gets the unit limits the scheduler is given to find other plans: the
+    /// limits of the options, the same without summer terms, and lower
+    /// limits that spread the units of the plan over more terms.
+    fn get_alternative_unit_limits(
+        &self,
+        terms: &[Term],
+        options: &ScheduleOptions,
+    ) -> Vec<[Credits; 4]> {
+        let mut unit_limits: Vec<[Credits; 4]> = vec![options.unit_limits];
+
+        let mut no_summer_limits: [Credits; 4] = options.unit_limits;
+        no_summer_limits[TermType::Summer as usize] = Credits::ZERO;
+        unit_limits.push(no_summer_limits);
+
+        let total_units: u64 = terms.iter().map(|x| x.units.hundredths() as u64).sum();
+        for extra_terms_count in 0..BALANCED_VARIANTS_COUNT {
+            let terms_count: u64 = (terms.len() + extra_terms_count) as u64;
+            let term_units: u64 = total_units.div_ceil(terms_count.max(1));
+            let term_units: Credits =
+                Credits::from_hundredths(term_units.min(u32::MAX as u64) as u32);
+
+            unit_limits.push(options.unit_limits.map(|x| x.min(term_units)));
+        }
+
+        let mut seen_limits: Vec<[Credits; 4]> = Vec::new();
+        unit_limits.retain(|x| {
+            let is_new = !seen_limits.contains(x);
+            seen_limits.push(*x);
+            is_new
+        });
+
+        unit_limits
+    }
+
+    /// Samples up to `count` distinct plans for the courses, ranked by the
+    /// objective. This is a heuristic rather than a search of every plan:
+    /// the scheduler is run again with the courses in a few fixed orders and
+    /// with tighter unit limits, so the best plan there is may be missed.
+    /// Each plan found follows every rule of the catalog and the options,
+    /// and the same catalog always gives the same plans.
+    pub fn sample_alternative_plans(
+        &self,
+        options: &ScheduleOptions,
+        objective: Objective,
+        count: usize,
+    ) -> Vec<AlternativePlan> {
+        let mut base_options: ScheduleOptions = options.clone();
+        base_options.course_order = self.get_course_names();
+
+        let base_terms: Vec<Term> = match self.get_terms_with(&base_options) {
+            Ok(x) => x,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut seen_plans: HashSet<Vec<(String, TracedTerm)>> = HashSet::new();
+        let mut plans: Vec<AlternativePlan> = Vec::new();
+        let mut options: ScheduleOptions = options.clone();
+        options.is_traced = false;
+
+        for unit_limits in self.get_alternative_unit_limits(&base_terms, &options) {
+            for course_order in self.get_alternative_course_orders() {
+                let mut alternative_options: ScheduleOptions = options.clone();
+                alternative_options.unit_limits = unit_limits;
+                alternative_options.course_order = course_order;
+
+                let mut terms: Vec<Term> = match self.get_terms_with(&alternative_options) {
+                    Ok(x) => x,
+                    Err(_) => continue,
+                };
+                if !seen_plans.insert(get_plan_key(&terms)) {
+                    continue;
+                }
+
+                for term in &mut terms {
+                    term.unit_limit = options.unit_limit(&term.term_type);
+                }
+
+                plans.push(AlternativePlan {
+                    score: PlanScore::new(&terms, &options.start_term),
+                    terms,
+                });
+            }
+        }
+
+        plans.sort_by_key(|x| x.score.get_rank(objective));
+        plans.truncate(count);
+
+        plans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+
+    #[test]
+    fn test_sample_alternative_plans() {
+        let mut courses: Courses = Courses::new();
+
+        for course_name in ["CS 101", "CS 201", "ART 1", "ART 2", "ART 3"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options.start_at(&TermType::Spring);
+
+        let plans: Vec<AlternativePlan> =
+            courses.sample_alternative_plans(&options, Objective::FewestTerms, 20);
+        assert!(plans.len() > 2);
+        assert_eq!(
+            plans[0].score,
+            PlanScore {
+                terms_count: 3,
+                unit_spread: Credits::from(4),
+                summer_terms_count: 1,
+            }
+        );
+
+        for plan in &plans {
+            assert!(courses
+                .validate_terms_with(&plan.terms, &options)
+                .is_empty());
+        }
+        assert!(!plans[0].diff(&plans[1]).is_empty());
+        assert_eq!(
+            courses.sample_alternative_plans(&options, Objective::FewestTerms, 20),
+            plans
+        );
+
+        let plans: Vec<AlternativePlan> =
+            courses.sample_alternative_plans(&options, Objective::MostBalanced, 2);
+        assert_eq!(plans.len(), 2);
+        assert_eq!(plans[0].score.unit_spread, Credits::ZERO);
+        assert_eq!(plans[0].score.terms_count, 5);
+
+        let plans: Vec<AlternativePlan> =
+            courses.sample_alternative_plans(&options, Objective::FewestSummers, 1);
+        assert_eq!(
+            plans[0].score,
+            PlanScore {
+                terms_count: 4,
+                unit_spread: Credits::from(4),
+                summer_terms_count: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,540 @@
+//! A full-screen board showing a plan as a column per term, where courses
+//! can be carried from term to term while the rules the plan breaks are
+//! listed underneath.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use cle_rust::{Courses, Credits, ScheduleOptions, Term, TermType, Violation};
+
+const COLUMN_WIDTH: u16 = 26;
+
+const HELP: &str = "←/→ term  ↑/↓ course  enter pick up/drop  esc put back  s save  q quit";
+
+fn get_next_term_for(term: &TermType) -> TermType {
+    match term {
+        TermType::Fall => TermType::Winter,
+        TermType::Winter => TermType::Spring,
+        TermType::Spring => TermType::Summer,
+        TermType::Summer => TermType::Fall,
+    }
+}
+
+/// A course picked up from a term, which moves along with the selected term
+/// until it is dropped.
+struct CarriedCourse {
+    name: String,
+    units: Credits,
+    from_term: usize,
+}
+
+/// The plan being edited, with what is selected and carried on the board.
+pub struct Board {
+    courses: Courses,
+    options: ScheduleOptions,
+    terms: Vec<Term>,
+    path: PathBuf,
+    violations: Vec<Violation>,
+    selected_term: usize,
+    selected_course: usize,
+    carried_course: Option<CarriedCourse>,
+    is_modified: bool,
+    status: String,
+}
+
+impl Board {
+    pub fn new(
+        courses: Courses,
+        options: ScheduleOptions,
+        terms: Vec<Term>,
+        path: PathBuf,
+    ) -> Board {
+        let mut board = Board {
+            courses,
+            options,
+            terms,
+            path,
+            violations: Vec::new(),
+            selected_term: 0,
+            selected_course: 0,
+            carried_course: None,
+            is_modified: false,
+            status: String::new(),
+        };
+        board.check();
+
+        board
+    }
+
+    fn check(&mut self) {
+        self.violations = self.courses.validate_terms_with(&self.terms, &self.options);
+    }
+
+    /// Moves the carried course into the given term, adding a term after the
+    /// last one when needed.
+    fn carry_to(&mut self, term: usize) {
+        let carried_course = match &self.carried_course {
+            Some(x) => x,
+            None => return,
+        };
+
+        if term == self.terms.len() {
+            let last_term: &Term = &self.terms[term - 1];
+            let term_type: TermType = get_next_term_for(last_term.term_type());
+            let year: u16 = match term_type {
+                TermType::Fall => last_term.year().saturating_add(1),
+                _ => last_term.year(),
+            };
+
+            let mut new_term = Term::new(&term_type, self.options.unit_limit(&term_type));
+            new_term.set_year(year);
+            self.terms.push(new_term);
+        }
+
+        let course = match self.courses.get_course(&carried_course.name) {
+            Some(x) => x,
+            None => return,
+        };
+        let units: Credits = carried_course.units;
+
+        self.terms[self.selected_term].remove_course(&course.name);
+        if self.terms[term].add_units(course, units) != Ok(true) {
+            // The units overflowed or the term already holds the course as
+            // many times as it can be taken, so put it back where it was.
+            let _ = self.terms[self.selected_term].add_units(course, units);
+            return;
+        }
+
+        self.selected_term = term;
+        self.selected_course = self.terms[term].courses().len() - 1;
+        self.is_modified = true;
+        self.check();
+    }
+
+    /// Picks up the selected course, or drops the carried one.
+    fn pick_up_or_drop(&mut self) {
+        if self.carried_course.take().is_some() {
+            while self.terms.len() > 1 && self.terms.last().is_some_and(|x| x.is_empty()) {
+                self.terms.pop();
+            }
+            self.status = String::from("Dropped the course");
+            return;
+        }
+
+        let (name, units) = match self
+            .terms
+            .get(self.selected_term)
+            .and_then(|x| x.courses().get(self.selected_course))
+        {
+            Some(x) => x.clone(),
+            None => return,
+        };
+
+        if self.courses.get_course(&name).is_none() {
+            self.status = format!("{} is not in the catalog, so it cannot be moved", name);
+            return;
+        }
+
+        self.status = format!("Carrying {}; move it with ←/→ and drop it with enter", name);
+        self.carried_course = Some(CarriedCourse {
+            name,
+            units,
+            from_term: self.selected_term,
+        });
+    }
+
+    fn put_back(&mut self) {
+        let from_term: usize = match &self.carried_course {
+            Some(x) => x.from_term,
+            None => return,
+        };
+
+        self.carry_to(from_term);
+        self.pick_up_or_drop();
+        self.status = String::from("Put the course back");
+    }
+
+    fn save(&mut self) {
+        let saved = serde_json::to_string_pretty(&self.terms)
+            .map_err(|e| e.to_string())
+            .and_then(|x| fs::write(&self.path, x + "\n").map_err(|e| e.to_string()));
+
+        self.status = match saved {
+            Ok(_) => {
+                self.is_modified = false;
+                format!("Saved the plan to {}", self.path.display())
+            }
+            Err(e) => format!("Could not save to {}: {}", self.path.display(), e),
+        };
+    }
+
+    /// Acts on a key press, returning true once the board should close.
+    fn handle_key(&mut self, key: KeyCode) -> bool {
+        let is_carrying: bool = self.carried_course.is_some();
+        let last_status: String = std::mem::take(&mut self.status);
+
+        match key {
+            KeyCode::Left | KeyCode::Char('h') if self.selected_term > 0 => match is_carrying {
+                true => self.carry_to(self.selected_term - 1),
+                false => {
+                    self.selected_term -= 1;
+                    self.selected_course = 0;
+                }
+            },
+            KeyCode::Right | KeyCode::Char('l') => match is_carrying {
+                true => self.carry_to(self.selected_term + 1),
+                false if self.selected_term + 1 < self.terms.len() => {
+                    self.selected_term += 1;
+                    self.selected_course = 0;
+                }
+                false => {}
+            },
+            KeyCode::Up | KeyCode::Char('k') if !is_carrying => {
+                self.selected_course = self.selected_course.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if !is_carrying => {
+                let courses_count: usize = self.terms[self.selected_term].courses().len();
+                if self.selected_course + 1 < courses_count {
+                    self.selected_course += 1;
+                }
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => self.pick_up_or_drop(),
+            KeyCode::Esc => self.put_back(),
+            KeyCode::Char('s') if !is_carrying => self.save(),
+            KeyCode::Char('q') if !is_carrying => {
+                if !self.is_modified || last_status.starts_with("Unsaved") {
+                    return true;
+                }
+
+                self.status = String::from("Unsaved changes; press q again to quit without saving");
+            }
+            _ => {}
+        }
+
+        false
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let violations_height: u16 = (self.violations.len() as u16).clamp(1, 6) + 2;
+        let [board_area, violations_area, status_area] = Layout::vertical([
+            Constraint::Min(4),
+            Constraint::Length(violations_height),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        self.draw_terms(frame, board_area);
+
+        let violation_lines: Vec<Line> = match self.violations.is_empty() {
+            true => vec![Line::styled(
+                "The plan follows every rule of the catalog",
+                Style::default().fg(Color::Green),
+            )],
+            false => self
+                .violations
+                .iter()
+                .map(|x| Line::from(x.to_string()))
+                .collect(),
+        };
+        frame.render_widget(
+            Paragraph::new(violation_lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Problems ({}) ", self.violations.len())),
+            ),
+            violations_area,
+        );
+
+        let status: &str = match self.status.is_empty() {
+            true => HELP,
+            false => &self.status,
+        };
+        frame.render_widget(
+            Paragraph::new(status).style(Style::default().add_modifier(Modifier::DIM)),
+            status_area,
+        );
+    }
+
+    fn draw_terms(&self, frame: &mut Frame, area: Rect) {
+        let columns_count: usize = ((area.width / COLUMN_WIDTH) as usize).max(1);
+        let first_term: usize = (self.selected_term + 1).saturating_sub(columns_count);
+        let shown_terms: usize = columns_count.min(self.terms.len() - first_term);
+
+        let columns =
+            Layout::horizontal(vec![Constraint::Length(COLUMN_WIDTH); shown_terms]).split(area);
+
+        for (column, index) in columns.iter().zip(first_term..) {
+            let term: &Term = &self.terms[index];
+            let is_selected: bool = index == self.selected_term;
+            let problem_courses: HashSet<&str> = self
+                .violations
+                .iter()
+                .filter(|x| x.term() == Some(index))
+                .flat_map(|x| x.courses())
+                .collect();
+
+            let items: Vec<ListItem> = term
+                .courses()
+                .iter()
+                .map(|(name, units)| {
+                    let style = match problem_courses.contains(name.as_str()) {
+                        true => Style::default().fg(Color::Red),
+                        false => Style::default(),
+                    };
+                    ListItem::new(Span::styled(format!("{} ({})", name, units), style))
+                })
+                .collect();
+
+            let border_style = match (is_selected, term.units() > term.unit_limit()) {
+                (_, true) => Style::default().fg(Color::Red),
+                (true, false) => Style::default().fg(Color::Cyan),
+                (false, false) => Style::default(),
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(format!(
+                    " {}. {}, year {} ",
+                    index + 1,
+                    term.term_type(),
+                    term.year()
+                ))
+                .title_bottom(format!(" {} / {} units ", term.units(), term.unit_limit()));
+
+            let highlight_style = match self.carried_course.is_some() {
+                true => Style::default().fg(Color::Black).bg(Color::Yellow),
+                false => Style::default().add_modifier(Modifier::REVERSED),
+            };
+            let mut state = ListState::default();
+            if is_selected {
+                state.select(Some(self.selected_course));
+            }
+
+            frame.render_stateful_widget(
+                List::new(items)
+                    .block(block)
+                    .highlight_style(highlight_style),
+                *column,
+                &mut state,
+            );
+        }
+    }
+
+    fn run_loop(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && self.handle_key(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Shows the board until the user quits, restoring the terminal afterwards.
+pub fn run(mut board: Board) -> Result<ExitCode, String> {
+    if board.terms.is_empty() {
+        return Err(String::from("the plan has no terms to show"));
+    }
+
+    let mut terminal: DefaultTerminal =
+        ratatui::try_init().map_err(|e| format!("could not start the board: {}", e))?;
+    let result = board.run_loop(&mut terminal);
+    ratatui::restore();
+
+    result.map_err(|e| format!("the board stopped: {}", e))?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cle_rust::{catalog, Course};
+
+    fn select(board: &mut Board, course: &str) {
+        board.selected_term = board
+            .terms
+            .iter()
+            .position(|x| x.has_course(course))
+            .unwrap();
+        board.selected_course = board.terms[board.selected_term]
+            .courses()
+            .iter()
+            .position(|x| x.0 == course)
+            .unwrap();
+    }
+
+    fn messages(board: &Board) -> Vec<String> {
+        let mut messages: Vec<String> = board.violations.iter().map(|x| x.to_string()).collect();
+        messages.sort();
+
+        messages
+    }
+
+    #[test]
+    fn test_planned_board_has_no_violations() {
+        let courses: Courses = catalog::load_dsl(
+            "CS 101 (4) [Fall, Winter]
+CS 201 (4) requires CS 101 with CS 201L
+CS 201L (1)
+ART 1 (4) [Fall]
+",
+        )
+        .unwrap();
+        let options = ScheduleOptions::new([10, 10, 10, 10]);
+        let terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        let board = Board::new(courses, options, terms, PathBuf::from("plan.json"));
+
+        assert!(board.violations.is_empty());
+        assert_eq!(board.terms.len(), 2);
+    }
+
+    #[test]
+    fn test_carry_course() {
+        let courses: Courses = catalog::load_dsl(
+            "CS 101 (4) [Fall, Winter]
+CS 201 (4) requires CS 101 with CS 201L
+CS 201L (1)
+ART 1 (4) [Fall]
+",
+        )
+        .unwrap();
+        let options = ScheduleOptions::new([10, 10, 10, 10]);
+        let terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        let mut board = Board::new(courses, options, terms, PathBuf::from("plan.json"));
+
+        select(&mut board, "CS 101");
+        board.handle_key(KeyCode::Enter);
+        board.handle_key(KeyCode::Right);
+        assert!(board.terms[1].has_course("CS 101"));
+        assert_eq!(
+            messages(&board),
+            vec![
+                "CS 201 in term 2 needs CS 101 in an earlier term",
+                "CS 201L in term 2 needs CS 101 in an earlier term"
+            ]
+        );
+
+        board.handle_key(KeyCode::Right);
+        assert_eq!(board.terms.len(), 3);
+        assert_eq!(board.terms[2].term_type(), &TermType::Spring);
+        assert_eq!(
+            messages(&board),
+            vec![
+                "CS 101 in term 3 is not offered in Spring",
+                "CS 201 in term 2 needs CS 101 in an earlier term",
+                "CS 201L in term 2 needs CS 101 in an earlier term"
+            ]
+        );
+
+        board.handle_key(KeyCode::Esc);
+        assert!(board.terms[0].has_course("CS 101"));
+        assert_eq!(board.terms.len(), 2);
+        assert!(board.violations.is_empty());
+        assert!(board.carried_course.is_none());
+    }
+
+    #[test]
+    fn test_split_concurrents_and_unit_limit() {
+        let courses: Courses = catalog::load_dsl(
+            "CS 101 (4) [Fall, Winter]
+CS 201 (4) requires CS 101 with CS 201L
+CS 201L (1)
+ART 1 (4) [Fall]
+",
+        )
+        .unwrap();
+        let options = ScheduleOptions::new([10, 10, 10, 10]);
+        let terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        let mut board = Board::new(courses, options, terms, PathBuf::from("plan.json"));
+
+        select(&mut board, "CS 201L");
+        board.handle_key(KeyCode::Enter);
+        board.handle_key(KeyCode::Left);
+        board.handle_key(KeyCode::Enter);
+
+        assert_eq!(
+            messages(&board),
+            vec![
+                "CS 201 in term 2 must be taken in the same term as CS 201L",
+                "CS 201L in term 1 must be taken in the same term as CS 201",
+                "CS 201L in term 1 needs CS 101 in an earlier term"
+            ]
+        );
+
+        select(&mut board, "CS 201");
+        board.handle_key(KeyCode::Enter);
+        board.handle_key(KeyCode::Left);
+        board.handle_key(KeyCode::Enter);
+
+        assert_eq!(
+            messages(&board),
+            vec![
+                "CS 201 in term 1 needs CS 101 in an earlier term",
+                "CS 201L in term 1 needs CS 101 in an earlier term",
+                "Term 1 has 13 units, over its limit of 10"
+            ]
+        );
+        assert_eq!(board.terms.len(), 1);
+    }
+
+    #[test]
+    fn test_carry_course_over_repeat_limit() {
+        let mut courses: Courses = Courses::new();
+        let mut course: Course = Course::new(String::from("MUS 10"), 1);
+        course.repeatable(2);
+        courses.add_course(course.clone());
+
+        let options = ScheduleOptions::new([10, 10, 10, 10]);
+        let mut terms: Vec<Term> = vec![
+            Term::new(&TermType::Fall, 10),
+            Term::new(&TermType::Winter, 10),
+        ];
+        terms[0].add(&course).unwrap();
+        terms[1].add(&course).unwrap();
+        terms[1].add(&course).unwrap();
+
+        let mut board = Board::new(courses, options, terms, PathBuf::from("plan.json"));
+        board.handle_key(KeyCode::Enter);
+        board.handle_key(KeyCode::Right);
+
+        assert_eq!(board.selected_term, 0);
+        assert_eq!(board.terms[0].course_count("MUS 10"), 1);
+        assert_eq!(board.terms[1].course_count("MUS 10"), 2);
+    }
+
+    #[test]
+    fn test_quit_with_unsaved_changes() {
+        let courses: Courses = catalog::load_dsl(
+            "CS 101 (4) [Fall, Winter]
+CS 201 (4) requires CS 101 with CS 201L
+CS 201L (1)
+ART 1 (4) [Fall]
+",
+        )
+        .unwrap();
+        let options = ScheduleOptions::new([10, 10, 10, 10]);
+        let terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        let mut board = Board::new(courses, options, terms, PathBuf::from("plan.json"));
+        assert!(board.handle_key(KeyCode::Char('q')));
+
+        select(&mut board, "ART 1");
+        board.handle_key(KeyCode::Enter);
+        board.handle_key(KeyCode::Right);
+        board.handle_key(KeyCode::Enter);
+
+        assert!(!board.handle_key(KeyCode::Char('q')));
+        assert!(board.handle_key(KeyCode::Char('q')));
+    }
+}
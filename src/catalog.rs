@@ -0,0 +1,441 @@
+//! Loading a `Courses` catalog from files that advisors maintain by hand.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+#[cfg(feature = "csv")]
+mod csv;
+mod dsl;
+#[cfg(feature = "toml")]
+mod toml;
+
+#[cfg(feature = "csv")]
+pub use self::csv::{load_csv, CsvColumns, CsvImport};
+pub use self::dsl::load_dsl;
+#[cfg(feature = "toml")]
+pub use self::toml::load_toml;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CatalogErrorKind {
+    Syntax(String),
+    UnknownCourse(String),
+    DuplicateCourse(String),
+    BadTermName(String),
+    BadCredits(String),
+    BadPrerequisites(String),
+    MissingColumn(String),
+    ConcurrentInSequence(String),
+}
+
+/// A problem found while loading a catalog, along with the line it is on
+/// and, when known, the columns of the offending text. Both start from 1
+/// and the end column is exclusive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatalogError {
+    pub line: usize,
+    pub columns: Option<Range<usize>>,
+    pub kind: CatalogErrorKind,
+}
+
+impl CatalogError {
+    pub fn new(line: usize, kind: CatalogErrorKind) -> CatalogError {
+        CatalogError {
+            line,
+            columns: None,
+            kind,
+        }
+    }
+
+    pub fn with_columns(
+        line: usize,
+        columns: Range<usize>,
+        kind: CatalogErrorKind,
+    ) -> CatalogError {
+        CatalogError {
+            line,
+            columns: Some(columns),
+            kind,
+        }
+    }
+
+    /// Renders the error above the line of the catalog text it is on, with
+    /// the offending columns underlined when they are known.
+    pub fn snippet(&self, text: &str) -> String {
+        let source_line: &str = self
+            .line
+            .checked_sub(1)
+            .and_then(|x| text.lines().nth(x))
+            .unwrap_or("");
+        let gutter: String = " ".repeat(self.line.to_string().len());
+
+        let mut snippet: String =
+            format!("{}\n{} |\n{} | {}\n", self, gutter, self.line, source_line);
+
+        if let Some(columns) = &self.columns {
+            let underline_length: usize = columns.end.saturating_sub(columns.start).max(1);
+
+            snippet.push_str(&format!(
+                "{} | {}{}\n",
+                gutter,
+                " ".repeat(columns.start.saturating_sub(1)),
+                "^".repeat(underline_length)
+            ));
+        }
+
+        snippet
+    }
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.columns {
+            Some(columns) => write!(f, "line {}, column {}: ", self.line, columns.start)?,
+            None => write!(f, "line {}: ", self.line)?,
+        }
+
+        match &self.kind {
+            CatalogErrorKind::Syntax(message) => write!(f, "{}", message),
+            CatalogErrorKind::UnknownCourse(name) => write!(f, "unknown course '{}'", name),
+            CatalogErrorKind::DuplicateCourse(name) => {
+                write!(f, "course '{}' is listed more than once", name)
+            }
+            CatalogErrorKind::BadTermName(name) => write!(
+                f,
+                "'{}' is not a term, expected Fall, Winter, Spring or Summer",
+                name
+            ),
+            CatalogErrorKind::BadCredits(text) => write!(f, "'{}' is not a credit amount", text),
+            CatalogErrorKind::BadPrerequisites(message) => {
+                write!(f, "could not read prerequisites: {}", message)
+            }
+            CatalogErrorKind::MissingColumn(name) => write!(f, "missing column '{}'", name),
+            CatalogErrorKind::ConcurrentInSequence(name) => write!(
+                f,
+                "course '{}' is taken together with another, so it can not be in a sequence",
+                name
+            ),
+        }
+    }
+}
+
+impl Error for CatalogError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Open,
+    Close,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+}
+
+/// A token along with the byte range of the text it was read from.
+#[derive(Clone, Debug, PartialEq)]
+struct Lexeme {
+    token: Token,
+    span: Range<usize>,
+}
+
+/// A parse error along with the byte range of the text it is about.
+#[derive(Debug, PartialEq)]
+struct ParseError {
+    message: String,
+    span: Range<usize>,
+}
+
+impl ParseError {
+    fn new(message: &str, span: Range<usize>) -> ParseError {
+        ParseError {
+            message: message.to_string(),
+            span,
+        }
+    }
+}
+
+fn lex(text: &str) -> Vec<Lexeme> {
+    let mut lexemes: Vec<Lexeme> = Vec::new();
+    let mut word_start: Option<usize> = None;
+
+    for (index, character) in text.char_indices() {
+        let token = match character {
+            '(' => Some(Token::Open),
+            ')' => Some(Token::Close),
+            '[' => Some(Token::OpenBracket),
+            ']' => Some(Token::CloseBracket),
+            ',' | ';' => Some(Token::Comma),
+            _ if character.is_whitespace() => None,
+            _ => {
+                word_start.get_or_insert(index);
+                continue;
+            }
+        };
+
+        if let Some(start) = word_start.take() {
+            lexemes.push(Lexeme {
+                token: Token::Word(text[start..index].to_string()),
+                span: start..index,
+            });
+        }
+
+        if let Some(token) = token {
+            lexemes.push(Lexeme {
+                token,
+                span: index..index + character.len_utf8(),
+            });
+        }
+    }
+
+    if let Some(start) = word_start {
+        lexemes.push(Lexeme {
+            token: Token::Word(text[start..].to_string()),
+            span: start..text.len(),
+        });
+    }
+
+    lexemes
+}
+
+/// Walks through lexed text, remembering where the text ends so errors at
+/// the end still point somewhere.
+struct Cursor<'a> {
+    lexemes: &'a [Lexeme],
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(lexemes: &'a [Lexeme], end: usize) -> Cursor<'a> {
+        Cursor {
+            lexemes,
+            position: 0,
+            end,
+        }
+    }
+
+    fn peek(&self) -> Option<&'a Lexeme> {
+        self.lexemes.get(self.position)
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position >= self.lexemes.len()
+    }
+
+    fn next_span(&self) -> Range<usize> {
+        match self.peek() {
+            Some(x) => x.span.clone(),
+            None => self.end..self.end,
+        }
+    }
+
+    /// Moves past the next token if it is the given keyword, ignoring case.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Lexeme {
+                token: Token::Word(word),
+                ..
+            }) if word.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek().map(|x| &x.token) == Some(token) {
+            self.position += 1;
+            return true;
+        }
+
+        false
+    }
+}
+
+const KEYWORDS: [&str; 4] = ["and", "or", "requires", "with"];
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.iter().any(|x| word.eq_ignore_ascii_case(x))
+}
+
+/// Reads a course name, which is every word up to the next keyword or
+/// punctuation, such as "CS 201L".
+fn parse_course_name(cursor: &mut Cursor) -> Result<(String, Range<usize>), ParseError> {
+    let mut words: Vec<&str> = Vec::new();
+    let mut span: Range<usize> = cursor.next_span();
+
+    while let Some(Lexeme {
+        token: Token::Word(word),
+        span: word_span,
+    }) = cursor.peek()
+    {
+        if is_keyword(word) {
+            break;
+        }
+
+        words.push(word.trim_end_matches('.'));
+        span.end = word_span.end;
+        cursor.position += 1;
+    }
+
+    if words.is_empty() {
+        return Err(ParseError::new(
+            "expected a course name",
+            cursor.next_span(),
+        ));
+    }
+
+    Ok((words.join(" "), span))
+}
+
+/// A list of prerequisite choices, where each choice holds the courses
+/// joined by "or" along with where each course was named.
+type PrerequisiteChoices = Vec<Vec<(String, Range<usize>)>>;
+
+/// Reads a list of choices joined by "and" or commas, where each choice is
+/// one or more courses joined by "or".
+fn parse_prerequisite_all(cursor: &mut Cursor) -> Result<PrerequisiteChoices, ParseError> {
+    let mut choices: PrerequisiteChoices = parse_prerequisite_choice(cursor)?;
+
+    while cursor.eat_keyword("and") || cursor.eat(&Token::Comma) {
+        choices.extend(parse_prerequisite_choice(cursor)?);
+    }
+
+    Ok(choices)
+}
+
+fn parse_prerequisite_choice(cursor: &mut Cursor) -> Result<PrerequisiteChoices, ParseError> {
+    let start: usize = cursor.next_span().start;
+    let mut items: Vec<PrerequisiteChoices> = vec![parse_prerequisite_item(cursor)?];
+
+    while cursor.eat_keyword("or") {
+        items.push(parse_prerequisite_item(cursor)?);
+    }
+
+    if items.len() == 1 {
+        return Ok(items.remove(0));
+    }
+
+    let mut options: Vec<(String, Range<usize>)> = Vec::new();
+    for item in items {
+        if item.len() != 1 {
+            let end: usize = item
+                .last()
+                .and_then(|x| x.last())
+                .map_or(start, |x| x.1.end);
+
+            return Err(ParseError::new(
+                "courses joined by \"and\" cannot be one side of an \"or\"",
+                start..end,
+            ));
+        }
+
+        options.extend(item.into_iter().flatten());
+    }
+
+    Ok(vec![options])
+}
+
+fn parse_prerequisite_item(cursor: &mut Cursor) -> Result<PrerequisiteChoices, ParseError> {
+    let open_span: Range<usize> = cursor.next_span();
+
+    if !cursor.eat(&Token::Open) {
+        let (name, span) = parse_course_name(cursor)?;
+
+        return Ok(vec![vec![(name, span)]]);
+    }
+
+    let choices: PrerequisiteChoices = parse_prerequisite_all(cursor)?;
+
+    if !cursor.eat(&Token::Close) {
+        return Err(ParseError::new(
+            "missing closing parenthesis",
+            open_span.start..cursor.next_span().start,
+        ));
+    }
+
+    Ok(choices)
+}
+
+/// Parses prerequisite text such as "CS 101 and (MATH 20 or MATH 21)" into
+/// the prerequisite choices it lists. Each choice holds the courses joined
+/// by "or", so a choice with a single course is an ordinary prerequisite.
+/// Blank text and "None" list no prerequisites.
+pub fn parse_prerequisites(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let trimmed_text = text.trim();
+    if trimmed_text.is_empty() || trimmed_text.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    let lexemes: Vec<Lexeme> = lex(trimmed_text);
+    let mut cursor: Cursor = Cursor::new(&lexemes, trimmed_text.len());
+
+    let choices = parse_prerequisite_all(&mut cursor).map_err(|e| e.message)?;
+
+    if !cursor.is_at_end() {
+        return Err(String::from("unexpected text after the last course"));
+    }
+
+    Ok(choices
+        .into_iter()
+        .map(|x| x.into_iter().map(|y| y.0).collect())
+        .collect())
+}
+
+/// Gets the line number, starting from 1, that a byte offset in the text
+/// falls on.
+#[cfg_attr(not(feature = "toml"), allow(dead_code))]
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prerequisites() {
+        assert_eq!(parse_prerequisites(""), Ok(Vec::new()));
+        assert_eq!(parse_prerequisites("None"), Ok(Vec::new()));
+        assert_eq!(
+            parse_prerequisites("CS 101 and MATH 20"),
+            Ok(vec![
+                vec![String::from("CS 101")],
+                vec![String::from("MATH 20")]
+            ])
+        );
+        assert_eq!(
+            parse_prerequisites("CS 101 or CS 105."),
+            Ok(vec![vec![String::from("CS 101"), String::from("CS 105")]])
+        );
+        assert_eq!(
+            parse_prerequisites("CS 101, (MATH 20 OR MATH 21)"),
+            Ok(vec![
+                vec![String::from("CS 101")],
+                vec![String::from("MATH 20"), String::from("MATH 21")]
+            ])
+        );
+    }
+
+    #[test]
+    fn test_error_snippet_at_start() {
+        let error = CatalogError::with_columns(
+            0,
+            0..0,
+            CatalogErrorKind::MissingColumn(String::from("code")),
+        );
+
+        assert_eq!(
+            error.snippet("code,credits\n"),
+            "line 0, column 0: missing column 'code'\n  |\n0 | \n  | ^\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_prerequisites_errors() {
+        assert!(parse_prerequisites("CS 101 and").is_err());
+        assert!(parse_prerequisites("(CS 101 or CS 105").is_err());
+        assert!(parse_prerequisites("CS 101 or (MATH 20 and MATH 21)").is_err());
+    }
+}
@@ -0,0 +1,326 @@
+//! Imports registrar course exports, which list one course per CSV row with
+//! its code, credits, terms offered and prerequisite text.
+
+use std::collections::HashSet;
+
+use super::{parse_prerequisites, CatalogError, CatalogErrorKind};
+use crate::{Course, Courses, Credits, TermType};
+
+/// The header names of the columns to read each part of a course from.
+/// Terms and prerequisites are optional: when their column is missing from
+/// the file it is read as empty, and a course with no terms listed is
+/// offered every term.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvColumns {
+    pub code: String,
+    pub credits: String,
+    pub terms: Option<String>,
+    pub prerequisites: Option<String>,
+}
+
+impl Default for CsvColumns {
+    fn default() -> CsvColumns {
+        CsvColumns {
+            code: String::from("code"),
+            credits: String::from("credits"),
+            terms: Some(String::from("terms")),
+            prerequisites: Some(String::from("prerequisites")),
+        }
+    }
+}
+
+/// The courses imported from a CSV export, along with the rows or parts of
+/// rows that could not be interpreted and were left out, and anything that
+/// was imported as written but looks wrong.
+#[derive(Debug)]
+pub struct CsvImport {
+    pub courses: Courses,
+    pub skipped_rows: Vec<CatalogError>,
+    pub warnings: Vec<CatalogError>,
+}
+
+struct CsvRow {
+    line: usize,
+    course_name: String,
+    prerequisites: Vec<Vec<String>>,
+}
+
+/// Imports courses from CSV text. Only a malformed file or a missing column
+/// fails the whole import; rows that cannot be read are reported in
+/// `CsvImport::skipped_rows` instead. A missing optional column and a
+/// prerequisite choice naming an unknown course are reported in
+/// `CsvImport::warnings`.
+pub fn load_csv(text: &str, columns: &CsvColumns) -> Result<CsvImport, CatalogError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let headers = reader.headers().map_err(to_catalog_error)?.clone();
+    let find_column = |name: &String| -> Result<usize, CatalogError> {
+        headers
+            .iter()
+            .position(|x| x.eq_ignore_ascii_case(name))
+            .ok_or_else(|| CatalogError::new(1, CatalogErrorKind::MissingColumn(name.clone())))
+    };
+
+    let code_column: usize = find_column(&columns.code)?;
+    let credits_column: usize = find_column(&columns.credits)?;
+
+    let mut warnings: Vec<CatalogError> = Vec::new();
+    let mut find_optional_column = |name: &Option<String>| -> Option<usize> {
+        let name: &String = name.as_ref()?;
+        find_column(name).map_err(|e| warnings.push(e)).ok()
+    };
+    let terms_column: Option<usize> = find_optional_column(&columns.terms);
+    let prerequisites_column: Option<usize> = find_optional_column(&columns.prerequisites);
+
+    let mut courses: Courses = Courses::new();
+    let mut skipped_rows: Vec<CatalogError> = Vec::new();
+    let mut rows: Vec<CsvRow> = Vec::new();
+
+    for record in reader.records() {
+        let record = record.map_err(to_catalog_error)?;
+        let line: usize = record.position().map_or(1, |x| x.line() as usize);
+        let field = |column: Option<usize>| column.and_then(|x| record.get(x)).unwrap_or("");
+
+        let code: &str = field(Some(code_column));
+        if code.is_empty() {
+            skipped_rows.push(CatalogError::new(
+                line,
+                CatalogErrorKind::Syntax(String::from("missing course code")),
+            ));
+            continue;
+        }
+
+        if courses.master_list.contains_key(code) {
+            skipped_rows.push(CatalogError::new(
+                line,
+                CatalogErrorKind::DuplicateCourse(code.to_string()),
+            ));
+            continue;
+        }
+
+        let row = parse_row(
+            line,
+            code,
+            field(Some(credits_column)),
+            field(terms_column),
+            field(prerequisites_column),
+        );
+
+        match row {
+            Ok((course, prerequisites)) => {
+                rows.push(CsvRow {
+                    line,
+                    course_name: course.name.clone(),
+                    prerequisites,
+                });
+                courses.add_course(course);
+            }
+            Err(e) => skipped_rows.push(e),
+        }
+    }
+
+    for row in rows {
+        for choice in row.prerequisites {
+            let unknown_options: Vec<&String> = choice
+                .iter()
+                .filter(|x| !courses.master_list.contains_key(*x))
+                .collect();
+
+            // A choice keeps every option as written as long as one of them
+            // can be taken, since dropping options could leave a single one
+            // behind and turn the choice into a hard prerequisite.
+            let is_satisfiable: bool = unknown_options.len() < choice.len();
+            for unknown_option in unknown_options {
+                let error = CatalogError::new(
+                    row.line,
+                    CatalogErrorKind::UnknownCourse(unknown_option.clone()),
+                );
+
+                if is_satisfiable {
+                    warnings.push(error);
+                } else {
+                    skipped_rows.push(error);
+                }
+            }
+
+            if is_satisfiable {
+                courses.add_prerequisite_choice(&row.course_name, &choice);
+            }
+        }
+    }
+
+    skipped_rows.sort_by_key(|x| x.line);
+    warnings.sort_by_key(|x| x.line);
+
+    Ok(CsvImport {
+        courses,
+        skipped_rows,
+        warnings,
+    })
+}
+
+/// Reads the course in a row along with the prerequisite choices it lists.
+fn parse_row(
+    line: usize,
+    code: &str,
+    credits_text: &str,
+    terms_text: &str,
+    prerequisites_text: &str,
+) -> Result<(Course, Vec<Vec<String>>), CatalogError> {
+    let bad_credits =
+        || CatalogError::new(line, CatalogErrorKind::BadCredits(credits_text.to_string()));
+
+    let credit_range: Vec<&str> = credits_text
+        .split(['-', '–'])
+        .flat_map(|x| x.split(" to "))
+        .collect();
+    let mut course: Course = match credit_range.as_slice() {
+        [credits] => Course::new(
+            code.to_string(),
+            credits.parse::<Credits>().map_err(|_| bad_credits())?,
+        ),
+        [min_credits, max_credits] => {
+            let mut course = Course::new(
+                code.to_string(),
+                min_credits.parse::<Credits>().map_err(|_| bad_credits())?,
+            );
+            course.variable_credits(max_credits.parse::<Credits>().map_err(|_| bad_credits())?);
+
+            course
+        }
+        _ => return Err(bad_credits()),
+    };
+
+    let term_names: HashSet<&str> = terms_text
+        .split([',', ';', '/'])
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect();
+    for term_name in term_names {
+        let term: TermType = term_name.parse().map_err(|_| {
+            CatalogError::new(line, CatalogErrorKind::BadTermName(term_name.to_string()))
+        })?;
+
+        course.available_by(&term);
+    }
+
+    let prerequisites: Vec<Vec<String>> = parse_prerequisites(prerequisites_text)
+        .map_err(|e| CatalogError::new(line, CatalogErrorKind::BadPrerequisites(e)))?;
+
+    Ok((course, prerequisites))
+}
+
+fn to_catalog_error(error: ::csv::Error) -> CatalogError {
+    let line: usize = error.position().map_or(1, |x| x.line() as usize);
+
+    CatalogError::new(line, CatalogErrorKind::Syntax(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Term;
+
+    #[test]
+    fn test_load_csv() {
+        let text = "code,title,credits,terms,prerequisites
+CS 101,Intro to Programming,4,Fall/Winter,
+CS 105,Programming for Scientists,4,Spring,
+CS 201,Data Structures,4,\"Fall, Spring\",CS 101 or CS 105
+CS 290,Research,1-4,,CS 201 and MATH 20
+";
+
+        let import: CsvImport = load_csv(text, &CsvColumns::default()).unwrap();
+        assert_eq!(import.courses.len(), 4);
+        assert_eq!(
+            import.skipped_rows,
+            vec![CatalogError::new(
+                5,
+                CatalogErrorKind::UnknownCourse(String::from("MATH 20"))
+            )]
+        );
+
+        let research_name = String::from("CS 290");
+        assert_eq!(
+            import.courses.get_prerequisites(&research_name),
+            Some(vec![String::from("CS 201")].into_iter().collect())
+        );
+        assert_eq!(
+            import
+                .courses
+                .get_prerequisite_choices(&String::from("CS 201")),
+            Some(vec![vec![String::from("CS 101"), String::from("CS 105")]])
+        );
+
+        let completed_terms: Vec<Term> = import.courses.get_terms([8, 8, 8, 8]).unwrap();
+        assert!(completed_terms.last().unwrap().has_course(&research_name));
+    }
+
+    #[test]
+    fn test_load_csv_column_mapping() {
+        let text = "Course,Units,Requisites
+CS 101,4,
+CS 201,four,CS 101
+CS 202,4,CS 101 or
+";
+
+        let columns = CsvColumns {
+            code: String::from("Course"),
+            credits: String::from("Units"),
+            terms: None,
+            prerequisites: Some(String::from("Requisites")),
+        };
+
+        let import: CsvImport = load_csv(text, &columns).unwrap();
+        assert_eq!(import.courses.len(), 1);
+        assert_eq!(import.skipped_rows.len(), 2);
+        assert_eq!(
+            import.skipped_rows[0],
+            CatalogError::new(3, CatalogErrorKind::BadCredits(String::from("four")))
+        );
+        assert_eq!(import.skipped_rows[1].line, 4);
+    }
+
+    #[test]
+    fn test_load_csv_unknown_choice_option() {
+        let text = "code,credits,prerequisites
+CS 101,4,
+CS 201,4,CS 101 or CS 100
+";
+
+        let import: CsvImport = load_csv(text, &CsvColumns::default()).unwrap();
+        assert!(import.skipped_rows.is_empty());
+        assert_eq!(
+            import.warnings,
+            vec![
+                CatalogError::new(1, CatalogErrorKind::MissingColumn(String::from("terms"))),
+                CatalogError::new(3, CatalogErrorKind::UnknownCourse(String::from("CS 100"))),
+            ]
+        );
+
+        let data_structures_name = String::from("CS 201");
+        assert_eq!(
+            import.courses.get_prerequisites(&data_structures_name),
+            None
+        );
+        assert_eq!(
+            import
+                .courses
+                .get_prerequisite_choices(&data_structures_name),
+            Some(vec![vec![String::from("CS 101"), String::from("CS 100")]])
+        );
+    }
+
+    #[test]
+    fn test_load_csv_missing_column() {
+        let result = load_csv("code,title\nCS 101,Intro\n", &CsvColumns::default());
+
+        assert_eq!(
+            result.unwrap_err(),
+            CatalogError::new(1, CatalogErrorKind::MissingColumn(String::from("credits")))
+        );
+    }
+}
@@ -0,0 +1,378 @@
+//! A compact catalog format with one course per line:
+//!
+//! ```text
+//! # Comments run to the end of the line.
+//! CS 101 (4) [Fall, Winter]
+//! CS 201 (4) [Fall, Spring] requires CS 101 and (MATH 20 or MATH 21) with CS 201L
+//! CS 290 (1-4)
+//! ```
+//!
+//! Each line names a course, its credits in parentheses, and optionally the
+//! terms it is offered in brackets, its prerequisites after `requires` and
+//! the courses it must be taken with after `with`.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use super::{
+    lex, parse_course_name, parse_prerequisite_all, CatalogError, CatalogErrorKind, Cursor, Lexeme,
+    ParseError, PrerequisiteChoices, Token,
+};
+use crate::{Course, Courses, Credits, TermType};
+
+struct DslCourse {
+    line: usize,
+    prerequisites: PrerequisiteChoices,
+    concurrents: Vec<(String, Range<usize>)>,
+}
+
+/// Gets the columns, starting from 1, that a byte range of a line covers.
+fn columns_of(line_text: &str, span: &Range<usize>) -> Range<usize> {
+    let start: usize = line_text[..span.start].chars().count() + 1;
+    let end: usize = line_text[..span.end].chars().count() + 1;
+
+    start..end
+}
+
+fn parse_credits(word: &str) -> Option<(Credits, Credits)> {
+    match word.split_once('-') {
+        Some((min_credits, max_credits)) => {
+            Some((min_credits.parse().ok()?, max_credits.parse().ok()?))
+        }
+        None => {
+            let credits: Credits = word.parse().ok()?;
+
+            Some((credits, credits))
+        }
+    }
+}
+
+fn expect(cursor: &mut Cursor, token: &Token, message: &str) -> Result<(), ParseError> {
+    if !cursor.eat(token) {
+        return Err(ParseError::new(message, cursor.next_span()));
+    }
+
+    Ok(())
+}
+
+/// Reads the course on a line, returning the course along with the relations
+/// it lists.
+fn parse_line(
+    line: usize,
+    lexemes: &[Lexeme],
+    line_end: usize,
+) -> Result<(Course, DslCourse), (ParseError, CatalogErrorKind)> {
+    let syntax = |e: ParseError| {
+        let message = e.message.clone();
+        (e, CatalogErrorKind::Syntax(message))
+    };
+
+    let mut cursor: Cursor = Cursor::new(lexemes, line_end);
+
+    let (name, _) = parse_course_name(&mut cursor).map_err(syntax)?;
+
+    expect(
+        &mut cursor,
+        &Token::Open,
+        "expected credits in parentheses, such as (4)",
+    )
+    .map_err(syntax)?;
+
+    let credits_span: Range<usize> = cursor.next_span();
+    let (credits, max_credits) = match cursor.peek() {
+        Some(Lexeme {
+            token: Token::Word(word),
+            ..
+        }) => {
+            cursor.position += 1;
+
+            parse_credits(word).ok_or_else(|| {
+                (
+                    ParseError::new("bad credits", credits_span.clone()),
+                    CatalogErrorKind::BadCredits(word.clone()),
+                )
+            })?
+        }
+        _ => return Err(syntax(ParseError::new("expected credits", credits_span))),
+    };
+
+    expect(&mut cursor, &Token::Close, "expected ')' after the credits").map_err(syntax)?;
+
+    let mut course: Course = Course::new(name, credits);
+    course.variable_credits(max_credits);
+
+    if cursor.eat(&Token::OpenBracket) {
+        loop {
+            let term_span: Range<usize> = cursor.next_span();
+            let term_name: &String = match cursor.peek() {
+                Some(Lexeme {
+                    token: Token::Word(word),
+                    ..
+                }) => word,
+                _ => return Err(syntax(ParseError::new("expected a term", term_span))),
+            };
+            cursor.position += 1;
+
+            let term: TermType = term_name.parse().map_err(|_| {
+                (
+                    ParseError::new("bad term", term_span),
+                    CatalogErrorKind::BadTermName(term_name.clone()),
+                )
+            })?;
+            course.available_by(&term);
+
+            if cursor.eat(&Token::CloseBracket) {
+                break;
+            }
+
+            expect(
+                &mut cursor,
+                &Token::Comma,
+                "expected ',' or ']' after a term",
+            )
+            .map_err(syntax)?;
+        }
+    }
+
+    let mut prerequisites: PrerequisiteChoices = Vec::new();
+    if cursor.eat_keyword("requires") {
+        prerequisites = parse_prerequisite_all(&mut cursor).map_err(syntax)?;
+    }
+
+    let mut concurrents: Vec<(String, Range<usize>)> = Vec::new();
+    if cursor.eat_keyword("with") {
+        concurrents.push(parse_course_name(&mut cursor).map_err(syntax)?);
+
+        while cursor.eat(&Token::Comma) || cursor.eat_keyword("and") {
+            concurrents.push(parse_course_name(&mut cursor).map_err(syntax)?);
+        }
+    }
+
+    if !cursor.is_at_end() {
+        let span: Range<usize> = cursor.next_span().start..line_end;
+
+        return Err(syntax(ParseError::new(
+            "unexpected text, expected `requires`, `with` or the end of the line",
+            span,
+        )));
+    }
+
+    Ok((
+        course,
+        DslCourse {
+            line,
+            prerequisites,
+            concurrents,
+        },
+    ))
+}
+
+/// Loads a catalog written in the line-oriented format, reporting every
+/// error found with the line and columns it is on.
+pub fn load_dsl(text: &str) -> Result<Courses, Vec<CatalogError>> {
+    let mut courses: Courses = Courses::new();
+    let mut errors: Vec<CatalogError> = Vec::new();
+    let mut dsl_courses: Vec<(String, DslCourse)> = Vec::new();
+    let mut course_names: HashSet<String> = HashSet::new();
+
+    let source_lines: Vec<&str> = text.lines().collect();
+
+    for (index, source_line) in source_lines.iter().enumerate() {
+        let line: usize = index + 1;
+        let line_text: &str = match source_line.find('#') {
+            Some(x) => &source_line[..x],
+            None => source_line,
+        };
+
+        let lexemes: Vec<Lexeme> = lex(line_text);
+        if lexemes.is_empty() {
+            continue;
+        }
+
+        let line_end: usize = line_text.trim_end().len();
+
+        // The name is recorded before the rest of the line is read, so that a
+        // course is reported as a duplicate even when its first line has an
+        // error of its own.
+        if let Ok((name, name_span)) = parse_course_name(&mut Cursor::new(&lexemes, line_end)) {
+            if !course_names.insert(name.clone()) {
+                errors.push(CatalogError::with_columns(
+                    line,
+                    columns_of(line_text, &name_span),
+                    CatalogErrorKind::DuplicateCourse(name),
+                ));
+                continue;
+            }
+        }
+
+        match parse_line(line, &lexemes, line_end) {
+            Ok((course, dsl_course)) => {
+                dsl_courses.push((course.name.clone(), dsl_course));
+                courses.add_course(course);
+            }
+            Err((e, kind)) => errors.push(CatalogError::with_columns(
+                line,
+                columns_of(line_text, &e.span),
+                kind,
+            )),
+        }
+    }
+
+    for (course_name, dsl_course) in dsl_courses {
+        let line_text: &str = source_lines[dsl_course.line - 1];
+        let mut check_known = |name: &String, span: &Range<usize>| {
+            let is_known = course_names.contains(name);
+
+            if !is_known {
+                errors.push(CatalogError::with_columns(
+                    dsl_course.line,
+                    columns_of(line_text, span),
+                    CatalogErrorKind::UnknownCourse(name.clone()),
+                ));
+            }
+
+            is_known
+        };
+
+        let mut prerequisite_choices: Vec<Vec<String>> = Vec::new();
+        for choice in &dsl_course.prerequisites {
+            prerequisite_choices.push(
+                choice
+                    .iter()
+                    .filter(|x| check_known(&x.0, &x.1))
+                    .map(|x| x.0.clone())
+                    .collect(),
+            );
+        }
+
+        let concurrents: Vec<String> = dsl_course
+            .concurrents
+            .iter()
+            .filter(|x| check_known(&x.0, &x.1))
+            .map(|x| x.0.clone())
+            .collect();
+
+        for concurrent in &concurrents {
+            courses
+                .add_concurrency(&course_name, concurrent)
+                .expect("the DSL has no sequences");
+        }
+
+        for choice in &prerequisite_choices {
+            courses.add_prerequisite_choice(&course_name, choice);
+        }
+    }
+
+    if !errors.is_empty() {
+        errors.sort_by_key(|x| (x.line, x.columns.as_ref().map(|y| y.start)));
+        return Err(errors);
+    }
+
+    Ok(courses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Term;
+
+    #[test]
+    fn test_load_dsl() {
+        let text = "# Lower division
+CS 101 (4) [Fall, Winter]
+MATH 20 (4)
+MATH 21 (4) [Spring]
+CS 201 (4) [Fall, Spring] requires CS 101 and (MATH 20 or MATH 21) with CS 201L
+CS 201L (1.5)
+CS 290 (1-4)   # research
+";
+
+        let courses: Courses = load_dsl(text).unwrap();
+        assert_eq!(courses.len(), 6);
+
+        let course_name = String::from("CS 201");
+        assert_eq!(
+            courses.get_prerequisites(&course_name),
+            Some(vec![String::from("CS 101")].into_iter().collect())
+        );
+        assert_eq!(
+            courses.get_prerequisite_choices(&course_name),
+            Some(vec![vec![String::from("MATH 20"), String::from("MATH 21")]])
+        );
+        assert_eq!(
+            courses.get_concurrents_for(&course_name).unwrap().1,
+            Ok(Credits::from_hundredths(550))
+        );
+        assert!(courses.master_list[&String::from("CS 290")].is_variable());
+
+        let completed_terms: Vec<Term> = courses.get_terms([12, 12, 12, 12]).unwrap();
+        assert!(!completed_terms[0].has_course(&course_name));
+    }
+
+    #[test]
+    fn test_load_dsl_errors() {
+        let text = "CS 101 (4) [Fall, Autumn]
+CS 201 (four)
+CS 202 (4) requires CS 101 and CS 100
+CS 203 (4) requires (CS 101 or CS 202
+CS 101 (4)
+CS 201 (4)
+";
+
+        let errors: Vec<CatalogError> = load_dsl(text).unwrap_err();
+        for error in &errors {
+            println!("{}", error.snippet(text));
+        }
+
+        assert_eq!(
+            errors,
+            vec![
+                CatalogError::with_columns(
+                    1,
+                    19..25,
+                    CatalogErrorKind::BadTermName(String::from("Autumn"))
+                ),
+                CatalogError::with_columns(
+                    2,
+                    9..13,
+                    CatalogErrorKind::BadCredits(String::from("four"))
+                ),
+                CatalogError::with_columns(
+                    3,
+                    32..38,
+                    CatalogErrorKind::UnknownCourse(String::from("CS 100"))
+                ),
+                CatalogError::with_columns(
+                    4,
+                    21..38,
+                    CatalogErrorKind::Syntax(String::from("missing closing parenthesis"))
+                ),
+                CatalogError::with_columns(
+                    5,
+                    1..7,
+                    CatalogErrorKind::DuplicateCourse(String::from("CS 101"))
+                ),
+                CatalogError::with_columns(
+                    6,
+                    1..7,
+                    CatalogErrorKind::DuplicateCourse(String::from("CS 201"))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_snippet() {
+        let text = "CS 101 (4) [Fall, Autumn]\n";
+
+        let errors: Vec<CatalogError> = load_dsl(text).unwrap_err();
+        assert_eq!(
+            errors[0].snippet(text),
+            "line 1, column 19: 'Autumn' is not a term, expected Fall, Winter, Spring or Summer
+  |
+1 | CS 101 (4) [Fall, Autumn]
+  |                   ^^^^^^
+"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! A TOML catalog lists each course as a `[[course]]` table:
+//!
+//! ```toml
+//! [[course]]
+//! name = "CS 201"
+//! credits = 4
+//! terms = ["Fall", "Spring"]
+//! prerequisites = ["CS 101"]
+//! concurrent = ["CS 201L"]
+//! ```
+//!
+//! Courses may also set `max_credits`, `repeat_limit`, `target_credits`,
+//! `equivalent`, `recommended` and `preferred_term`, and `[[sequence]]`
+//! tables list `courses` to take in consecutive terms.
+
+use std::collections::HashSet;
+
+use ::toml::Spanned;
+use serde::Deserialize;
+
+use super::{line_of, CatalogError, CatalogErrorKind};
+use crate::{Course, Courses, Credits, TermType};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    course: Vec<CourseEntry>,
+    #[serde(default)]
+    sequence: Vec<SequenceEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CourseEntry {
+    name: Spanned<String>,
+    credits: Credits,
+    max_credits: Option<Credits>,
+    repeat_limit: Option<u8>,
+    target_credits: Option<Credits>,
+    #[serde(default)]
+    terms: Vec<Spanned<String>>,
+    #[serde(default)]
+    prerequisites: Vec<Spanned<String>>,
+    #[serde(default)]
+    concurrent: Vec<Spanned<String>>,
+    #[serde(default)]
+    equivalent: Vec<String>,
+    #[serde(default)]
+    recommended: Vec<Spanned<String>>,
+    preferred_term: Option<Spanned<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SequenceEntry {
+    courses: Vec<Spanned<String>>,
+}
+
+/// Loads a catalog from TOML text, reporting every unknown course, duplicate
+/// course and bad term name found rather than stopping at the first one.
+pub fn load_toml(text: &str) -> Result<Courses, Vec<CatalogError>> {
+    let catalog: CatalogFile = ::toml::from_str(text).map_err(|e| {
+        let line = e.span().map_or(1, |x| line_of(text, x.start));
+
+        vec![CatalogError::new(
+            line,
+            CatalogErrorKind::Syntax(e.message().to_string()),
+        )]
+    })?;
+
+    let mut courses: Courses = Courses::new();
+    let mut errors: Vec<CatalogError> = Vec::new();
+    let mut duplicate_entries: HashSet<usize> = HashSet::new();
+
+    for (index, entry) in catalog.course.iter().enumerate() {
+        let name: &String = entry.name.get_ref();
+
+        if courses.master_list.contains_key(name) {
+            errors.push(CatalogError::new(
+                line_of(text, entry.name.span().start),
+                CatalogErrorKind::DuplicateCourse(name.clone()),
+            ));
+            duplicate_entries.insert(index);
+            continue;
+        }
+
+        let mut course: Course = Course::new(name.clone(), entry.credits);
+
+        if let Some(max_credits) = entry.max_credits {
+            course.variable_credits(max_credits);
+        }
+
+        if let Some(repeat_limit) = entry.repeat_limit {
+            course.repeatable(repeat_limit);
+        }
+
+        if let Some(target_credits) = entry.target_credits {
+            course.set_target_credits(target_credits);
+        }
+
+        for term_name in &entry.terms {
+            match parse_term(text, term_name) {
+                Ok(term) => {
+                    course.available_by(&term);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if let Some(term_name) = &entry.preferred_term {
+            match parse_term(text, term_name) {
+                Ok(term) => courses.prefer_term(name, &term),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        for equivalent in &entry.equivalent {
+            courses.add_equivalence(name, equivalent);
+        }
+
+        courses.add_course(course);
+    }
+
+    for (index, entry) in catalog.course.iter().enumerate() {
+        if duplicate_entries.contains(&index) {
+            continue;
+        }
+
+        let name: &String = entry.name.get_ref();
+
+        for prerequisite in &entry.prerequisites {
+            if check_known(text, &courses, prerequisite, &mut errors) {
+                courses.add_prerequisite(name, prerequisite.get_ref());
+            }
+        }
+
+        for concurrent in &entry.concurrent {
+            if check_known(text, &courses, concurrent, &mut errors) {
+                courses
+                    .add_concurrency(name, concurrent.get_ref())
+                    .expect("sequences are added after every concurrency");
+            }
+        }
+
+        for recommended in &entry.recommended {
+            if check_known(text, &courses, recommended, &mut errors) {
+                courses.add_recommendation(name, recommended.get_ref());
+            }
+        }
+    }
+
+    for sequence in &catalog.sequence {
+        let mut sequence_courses: Vec<String> = Vec::new();
+
+        for course_name in &sequence.courses {
+            if check_known(text, &courses, course_name, &mut errors) {
+                sequence_courses.push(course_name.get_ref().clone());
+            }
+        }
+
+        if !courses.add_sequence(&sequence_courses) {
+            let concurrent_course = sequence
+                .courses
+                .iter()
+                .find(|x| courses.get_concurrents_for(x.get_ref()).is_some());
+
+            if let Some(course_name) = concurrent_course {
+                errors.push(CatalogError::new(
+                    line_of(text, course_name.span().start),
+                    CatalogErrorKind::ConcurrentInSequence(course_name.get_ref().clone()),
+                ));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        errors.sort_by_key(|x| x.line);
+        return Err(errors);
+    }
+
+    Ok(courses)
+}
+
+fn parse_term(text: &str, term_name: &Spanned<String>) -> Result<TermType, CatalogError> {
+    term_name.get_ref().parse().map_err(|_| {
+        CatalogError::new(
+            line_of(text, term_name.span().start),
+            CatalogErrorKind::BadTermName(term_name.get_ref().clone()),
+        )
+    })
+}
+
+/// Checks that a course named in a relation is in the catalog, either itself
+/// or through an equivalent course, recording an error when it is not.
+fn check_known(
+    text: &str,
+    courses: &Courses,
+    course_name: &Spanned<String>,
+    errors: &mut Vec<CatalogError>,
+) -> bool {
+    let name: &String = course_name.get_ref();
+
+    let is_known = courses.master_list.contains_key(name)
+        || courses
+            .get_equivalents_for(name)
+            .is_some_and(|x| x.iter().any(|y| courses.master_list.contains_key(y)));
+
+    if !is_known {
+        errors.push(CatalogError::new(
+            line_of(text, course_name.span().start),
+            CatalogErrorKind::UnknownCourse(name.clone()),
+        ));
+    }
+
+    is_known
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Term;
+
+    #[test]
+    fn test_load_toml() {
+        let text = r#"
+[[course]]
+name = "CS 101"
+credits = 4
+terms = ["Fall", "Winter"]
+
+[[course]]
+name = "CS 201"
+credits = 4
+terms = ["fall", "spring"]
+prerequisites = ["CS 101"]
+concurrent = ["CS 201L"]
+
+[[course]]
+name = "CS 201L"
+credits = 1.5
+"#;
+
+        let courses: Courses = load_toml(text).unwrap();
+        assert_eq!(courses.len(), 3);
+
+        let lab_name = String::from("CS 201L");
+        assert_eq!(
+            courses.get_prerequisites(&lab_name),
+            Some(vec![String::from("CS 101")].into_iter().collect())
+        );
+        assert_eq!(
+            courses.get_concurrents_for(&lab_name).unwrap().1,
+            Ok(Credits::from_hundredths(550))
+        );
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+        assert!(completed_terms[0].has_course("CS 101"));
+    }
+
+    #[test]
+    fn test_load_toml_errors() {
+        let text = r#"[[course]]
+name = "CS 101"
+credits = 4
+terms = ["Fall", "Autumn"]
+
+[[course]]
+name = "CS 101"
+credits = 4
+
+[[course]]
+name = "CS 201"
+credits = 4
+prerequisites = ["CS 100"]
+"#;
+
+        let errors: Vec<CatalogError> = load_toml(text).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                CatalogError::new(4, CatalogErrorKind::BadTermName(String::from("Autumn"))),
+                CatalogError::new(7, CatalogErrorKind::DuplicateCourse(String::from("CS 101"))),
+                CatalogError::new(13, CatalogErrorKind::UnknownCourse(String::from("CS 100"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_toml_concurrent_in_sequence() {
+        let text = r#"[[course]]
+name = "CS 490"
+credits = 4
+concurrent = ["CS 490L"]
+
+[[course]]
+name = "CS 490L"
+credits = 1
+
+[[course]]
+name = "CS 491"
+credits = 4
+
+[[sequence]]
+courses = ["CS 490", "CS 491"]
+"#;
+
+        let errors: Vec<CatalogError> = load_toml(text).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![CatalogError::new(
+                15,
+                CatalogErrorKind::ConcurrentInSequence(String::from("CS 490"))
+            )]
+        );
+    }
+
+    #[test]
+    fn test_load_toml_syntax_error() {
+        let text = "[[course]]\nname = \"CS 101\"\ncredits = \"four\"\n";
+
+        let errors: Vec<CatalogError> = load_toml(text).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 3);
+    }
+
+    #[test]
+    fn test_load_toml_equivalent_prerequisite() {
+        let text = r#"
+[[course]]
+name = "CS 340"
+credits = 4
+equivalent = ["MATH 340"]
+
+[[course]]
+name = "CS 440"
+credits = 4
+prerequisites = ["MATH 340"]
+"#;
+
+        assert!(load_toml(text).is_ok());
+    }
+}
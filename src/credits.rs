@@ -0,0 +1,282 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+const HUNDREDTHS_PER_CREDIT: u32 = 100;
+
+/// An amount of credits, kept in hundredths of a credit so that half and
+/// quarter credit courses add up exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Credits(u32);
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CreditError {
+    Overflow,
+    Invalid(String),
+}
+
+impl fmt::Display for CreditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CreditError::Overflow => write!(f, "credit total is too large"),
+            CreditError::Invalid(text) => write!(f, "'{}' is not a credit amount", text),
+        }
+    }
+}
+
+impl Error for CreditError {}
+
+impl Credits {
+    pub const ZERO: Credits = Credits(0);
+    pub const MAX: Credits = Credits(u32::MAX);
+
+    pub fn from_hundredths(hundredths: u32) -> Credits {
+        Credits(hundredths)
+    }
+
+    pub fn hundredths(&self) -> u32 {
+        self.0
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        f64::from(self.0) / f64::from(HUNDREDTHS_PER_CREDIT)
+    }
+
+    pub fn checked_add(self, other: Credits) -> Result<Credits, CreditError> {
+        match self.0.checked_add(other.0) {
+            Some(x) => Ok(Credits(x)),
+            None => Err(CreditError::Overflow),
+        }
+    }
+
+    pub fn saturating_sub(self, other: Credits) -> Credits {
+        Credits(self.0.saturating_sub(other.0))
+    }
+
+    pub fn checked_sum<I>(credits: I) -> Result<Credits, CreditError>
+    where
+        I: IntoIterator<Item = Credits>,
+    {
+        credits
+            .into_iter()
+            .try_fold(Credits::ZERO, |total, x| total.checked_add(x))
+    }
+}
+
+/// Converts whole credits, saturating at Credits::MAX for amounts too large
+/// to hold. Use `checked_add` or `str::parse` when overflow must be caught.
+impl From<u32> for Credits {
+    fn from(whole_credits: u32) -> Credits {
+        Credits(whole_credits.saturating_mul(HUNDREDTHS_PER_CREDIT))
+    }
+}
+
+/// Converts whole credits written as a bare integer literal, which Rust
+/// takes to be an i32 now that several integer types convert. Negative
+/// amounts become zero and large ones saturate like `From<u32>`.
+impl From<i32> for Credits {
+    fn from(whole_credits: i32) -> Credits {
+        Credits::from(u32::try_from(whole_credits).unwrap_or(0))
+    }
+}
+
+impl From<u16> for Credits {
+    fn from(whole_credits: u16) -> Credits {
+        Credits(u32::from(whole_credits) * HUNDREDTHS_PER_CREDIT)
+    }
+}
+
+impl From<u8> for Credits {
+    fn from(whole_credits: u8) -> Credits {
+        Credits(u32::from(whole_credits) * HUNDREDTHS_PER_CREDIT)
+    }
+}
+
+impl FromStr for Credits {
+    type Err = CreditError;
+
+    fn from_str(text: &str) -> Result<Credits, CreditError> {
+        let invalid = || CreditError::Invalid(text.to_string());
+        let trimmed = text.trim();
+
+        let (whole, fraction) = match trimmed.find('.') {
+            Some(index) => (&trimmed[..index], &trimmed[index + 1..]),
+            None => (trimmed, ""),
+        };
+
+        let is_digits = |x: &str| x.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty())
+            || !is_digits(whole)
+            || !is_digits(fraction)
+            || fraction.len() > 2
+        {
+            return Err(invalid());
+        }
+
+        let whole_credits: u32 = match whole {
+            "" => 0,
+            x => x.parse().map_err(|_| CreditError::Overflow)?,
+        };
+        let hundredths: u32 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+
+        whole_credits
+            .checked_mul(HUNDREDTHS_PER_CREDIT)
+            .and_then(|x| x.checked_add(hundredths))
+            .map(Credits)
+            .ok_or(CreditError::Overflow)
+    }
+}
+
+impl fmt::Display for Credits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / HUNDREDTHS_PER_CREDIT;
+        let hundredths = self.0 % HUNDREDTHS_PER_CREDIT;
+
+        if hundredths == 0 {
+            write!(f, "{}", whole)
+        } else if hundredths.is_multiple_of(10) {
+            write!(f, "{}.{}", whole, hundredths / 10)
+        } else {
+            write!(f, "{}.{:02}", whole, hundredths)
+        }
+    }
+}
+
+/// Whole credits serialize as integers and fractional credits as floats, so
+/// catalogs can write `credits = 4` or `credits = 1.5`. Strings such as
+/// `"1.5"` are accepted when deserializing as well.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Credits {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_multiple_of(HUNDREDTHS_PER_CREDIT) {
+            serializer.serialize_u32(self.0 / HUNDREDTHS_PER_CREDIT)
+        } else {
+            serializer.serialize_f64(self.as_f64())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Credits {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Credits, D::Error> {
+        use std::convert::TryFrom;
+
+        struct CreditsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CreditsVisitor {
+            type Value = Credits;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a non-negative credit amount with at most two decimals")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Credits, E> {
+                value
+                    .checked_mul(u64::from(HUNDREDTHS_PER_CREDIT))
+                    .and_then(|x| u32::try_from(x).ok())
+                    .map(Credits)
+                    .ok_or_else(|| E::custom(CreditError::Overflow))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Credits, E> {
+                match u64::try_from(value) {
+                    Ok(x) => self.visit_u64(x),
+                    Err(_) => Err(E::custom(CreditError::Invalid(value.to_string()))),
+                }
+            }
+
+            // The shortest decimal text of the float is parsed like a string,
+            // so floats are held to the same rules, such as at most two
+            // decimals, instead of being rounded.
+            fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Credits, E> {
+                value.to_string().parse().map_err(E::custom)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Credits, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(CreditsVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_credits() {
+        assert_eq!("4".parse(), Ok(Credits::from(4)));
+        assert_eq!("1.5".parse(), Ok(Credits::from_hundredths(150)));
+        assert_eq!("0.25".parse(), Ok(Credits::from_hundredths(25)));
+        assert_eq!(".5".parse(), Ok(Credits::from_hundredths(50)));
+        assert_eq!(
+            "1.125".parse::<Credits>(),
+            Err(CreditError::Invalid(String::from("1.125")))
+        );
+        assert_eq!(
+            "-1".parse::<Credits>(),
+            Err(CreditError::Invalid(String::from("-1")))
+        );
+        assert_eq!("99999999999".parse::<Credits>(), Err(CreditError::Overflow));
+    }
+
+    #[test]
+    fn test_display_credits() {
+        assert_eq!(Credits::from(4).to_string(), "4");
+        assert_eq!(Credits::from_hundredths(150).to_string(), "1.5");
+        assert_eq!(Credits::from_hundredths(725).to_string(), "7.25");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_credits() {
+        assert_eq!(serde_json::to_string(&Credits::from(4)).unwrap(), "4");
+        assert_eq!(
+            serde_json::to_string(&Credits::from_hundredths(150)).unwrap(),
+            "1.5"
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Credits>("1.5").unwrap(),
+            Credits::from_hundredths(150)
+        );
+        assert_eq!(
+            serde_json::from_str::<Credits>("\"7.25\"").unwrap(),
+            Credits::from_hundredths(725)
+        );
+        assert!(serde_json::from_str::<Credits>("-2").is_err());
+
+        assert_eq!(
+            serde_json::from_str::<Credits>("0.1").unwrap(),
+            Credits::from_hundredths(10)
+        );
+        assert!(serde_json::from_str::<Credits>("1.125").is_err());
+        assert!(serde_json::from_str::<Credits>("-1.5").is_err());
+        assert!(serde_json::from_str::<Credits>("99999999999.5").is_err());
+    }
+
+    #[test]
+    fn test_credits_overflow() {
+        assert_eq!(
+            Credits::MAX.checked_add(Credits::from_hundredths(1)),
+            Err(CreditError::Overflow)
+        );
+        assert_eq!(
+            Credits::checked_sum(vec![Credits::from(300), Credits::from(300)]),
+            Ok(Credits::from(600))
+        );
+    }
+
+    #[test]
+    fn test_credits_from_integers() {
+        assert_eq!(Credits::from(4u8), Credits::from_hundredths(400));
+        assert_eq!(Credits::from(u16::MAX), Credits::from_hundredths(6_553_500));
+        assert_eq!(Credits::from(4u32), Credits::from(4));
+        assert_eq!(Credits::from(-4), Credits::ZERO);
+        assert_eq!(Credits::from(u32::MAX), Credits::MAX);
+    }
+}
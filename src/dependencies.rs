@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::Courses;
+
+impl Courses {
+    /// Gets the courses any prerequisite of the course names, counting every
+    /// option of a prerequisite choice, sorted.
+    fn get_direct_prerequisites(&self, course: &str) -> Vec<String> {
+        let mut prerequisites: Vec<String> = self
+            .prerequisites
+            .get_vec(course)
+            .cloned()
+            .unwrap_or_default();
+        prerequisites.extend(
+            self.prerequisite_choices
+                .get_vec(course)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .cloned(),
+        );
+        prerequisites.sort();
+        prerequisites.dedup();
+
+        prerequisites
+    }
+
+    /// Gets the courses naming the course as a prerequisite, either required
+    /// or as an option of a choice, sorted.
+    fn get_direct_dependents(&self, course: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = self
+            .prerequisites
+            .iter_all()
+            .filter(|x| x.1.iter().any(|y| y == course))
+            .map(|x| x.0.clone())
+            .collect();
+        dependents.extend(
+            self.prerequisite_choices
+                .iter_all()
+                .filter(|x| x.1.iter().flatten().any(|y| y == course))
+                .map(|x| x.0.clone()),
+        );
+        dependents.sort();
+        dependents.dedup();
+
+        dependents
+    }
+
+    fn get_reachable(&self, course: &str, is_forward: bool) -> HashSet<String> {
+        let mut found_courses: HashSet<String> = HashSet::new();
+        let mut pending_courses: Vec<String> = vec![course.to_string()];
+
+        while let Some(current_course) = pending_courses.pop() {
+            let next_courses: Vec<String> = match is_forward {
+                true => self.get_direct_dependents(&current_course),
+                false => self.get_direct_prerequisites(&current_course),
+            };
+
+            for next_course in next_courses {
+                if next_course != course && found_courses.insert(next_course.clone()) {
+                    pending_courses.push(next_course);
+                }
+            }
+        }
+
+        found_courses
+    }
+
+    /// Gets every course that has to come before the course, following
+    /// prerequisites of prerequisites. Each option of a prerequisite choice
+    /// is included, as any of them may be the one taken.
+    pub fn get_all_prerequisites(&self, course: &str) -> HashSet<String> {
+        self.get_reachable(course, false)
+    }
+
+    /// Gets every course that completing the course helps unlock, which are
+    /// the courses that have it as a direct or indirect prerequisite.
+    pub fn get_dependents(&self, course: &str) -> HashSet<String> {
+        self.get_reachable(course, true)
+    }
+
+    /// Gets the fewest courses to take one after another to reach the course,
+    /// starting from a course without prerequisites and ending with the
+    /// course itself. None is given when the course is not in the catalog or
+    /// every line of its prerequisites loops back on itself.
+    pub fn get_shortest_prerequisite_chain(&self, course: &str) -> Option<Vec<String>> {
+        self.master_list.get(course)?;
+
+        let mut next_courses: HashMap<String, String> = HashMap::new();
+        let mut pending_courses: VecDeque<String> = VecDeque::from([course.to_string()]);
+
+        while let Some(current_course) = pending_courses.pop_front() {
+            let prerequisites: Vec<String> = self.get_direct_prerequisites(&current_course);
+
+            if prerequisites.is_empty() {
+                let mut chain: Vec<String> = vec![current_course];
+
+                while let Some(next_course) = next_courses.get(chain.last().unwrap()) {
+                    chain.push(next_course.clone());
+                }
+
+                return Some(chain);
+            }
+
+            for prerequisite in prerequisites {
+                if prerequisite != course && !next_courses.contains_key(&prerequisite) {
+                    next_courses.insert(prerequisite.clone(), current_course.clone());
+                    pending_courses.push_back(prerequisite);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Gets the most courses that have to be taken one after another to
+    /// reach the course, ending with the course itself. A prerequisite
+    /// choice follows its option with the shortest chain, since that is the
+    /// one that can be finished first, so the length of the chain is the
+    /// fewest terms the course can be planned in.
+    pub fn get_longest_prerequisite_chain(&self, course: &str) -> Option<Vec<String>> {
+        self.master_list.get(course)?;
+
+        let mut chains: HashMap<String, Vec<String>> = HashMap::new();
+        let mut chain: Vec<String> =
+            self.find_longest_chain(course, &mut chains, &mut HashSet::new());
+        chain.reverse();
+
+        Some(chain)
+    }
+
+    /// Finds the longest chain of prerequisites ending at the course, from
+    /// the course backwards. Prerequisites already on the way to the course
+    /// are skipped, so a loop of prerequisites is only followed once.
+    fn find_longest_chain(
+        &self,
+        course: &str,
+        chains: &mut HashMap<String, Vec<String>>,
+        visiting_courses: &mut HashSet<String>,
+    ) -> Vec<String> {
+        if let Some(chain) = chains.get(course) {
+            return chain.clone();
+        }
+
+        visiting_courses.insert(course.to_string());
+
+        let mut required_courses: Vec<String> = self
+            .prerequisites
+            .get_vec(course)
+            .cloned()
+            .unwrap_or_default();
+        required_courses.sort();
+        required_courses.dedup();
+
+        let mut longest_chain: Vec<String> = Vec::new();
+        for prerequisite in required_courses {
+            if visiting_courses.contains(&prerequisite) {
+                continue;
+            }
+
+            let prerequisite_chain: Vec<String> =
+                self.find_longest_chain(&prerequisite, chains, visiting_courses);
+            if prerequisite_chain.len() > longest_chain.len() {
+                longest_chain = prerequisite_chain;
+            }
+        }
+
+        let choices: Vec<Vec<String>> = self
+            .prerequisite_choices
+            .get_vec(course)
+            .cloned()
+            .unwrap_or_default();
+        for choice in choices {
+            let mut shortest_chain: Option<Vec<String>> = None;
+
+            for option in choice {
+                if visiting_courses.contains(&option) {
+                    continue;
+                }
+
+                let option_chain: Vec<String> =
+                    self.find_longest_chain(&option, chains, visiting_courses);
+                if shortest_chain
+                    .as_ref()
+                    .is_none_or(|x| option_chain.len() < x.len())
+                {
+                    shortest_chain = Some(option_chain);
+                }
+            }
+
+            if let Some(shortest_chain) = shortest_chain {
+                if shortest_chain.len() > longest_chain.len() {
+                    longest_chain = shortest_chain;
+                }
+            }
+        }
+
+        visiting_courses.remove(course);
+
+        let mut chain: Vec<String> = vec![course.to_string()];
+        chain.extend(longest_chain);
+        chains.insert(course.to_string(), chain.clone());
+
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+
+    fn sorted(courses: HashSet<String>) -> Vec<String> {
+        let mut courses: Vec<String> = courses.into_iter().collect();
+        courses.sort();
+
+        courses
+    }
+
+    #[test]
+    fn test_get_all_prerequisites() {
+        let mut courses: Courses = Courses::new();
+
+        for course_name in [
+            "MATH 1", "MATH 2", "CS 101", "CS 105", "CS 201", "CS 301", "CS 350", "ART 1",
+        ] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite(&String::from("MATH 2"), &String::from("MATH 1"));
+        courses.add_prerequisite(&String::from("CS 105"), &String::from("MATH 2"));
+        courses.add_prerequisite_choice(
+            &String::from("CS 201"),
+            &[String::from("CS 101"), String::from("CS 105")],
+        );
+        courses.add_prerequisite(&String::from("CS 301"), &String::from("CS 201"));
+        courses.add_prerequisite(&String::from("CS 350"), &String::from("CS 201"));
+        courses.add_prerequisite(&String::from("CS 350"), &String::from("MATH 2"));
+
+        assert_eq!(
+            sorted(courses.get_all_prerequisites("CS 301")),
+            vec!["CS 101", "CS 105", "CS 201", "MATH 1", "MATH 2"]
+        );
+        assert!(courses.get_all_prerequisites("ART 1").is_empty());
+        assert!(courses.get_all_prerequisites("CS 999").is_empty());
+    }
+
+    #[test]
+    fn test_get_dependents() {
+        let mut courses: Courses = Courses::new();
+
+        for course_name in [
+            "MATH 1", "MATH 2", "CS 101", "CS 105", "CS 201", "CS 301", "CS 350", "ART 1",
+        ] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite(&String::from("MATH 2"), &String::from("MATH 1"));
+        courses.add_prerequisite(&String::from("CS 105"), &String::from("MATH 2"));
+        courses.add_prerequisite_choice(
+            &String::from("CS 201"),
+            &[String::from("CS 101"), String::from("CS 105")],
+        );
+        courses.add_prerequisite(&String::from("CS 301"), &String::from("CS 201"));
+        courses.add_prerequisite(&String::from("CS 350"), &String::from("CS 201"));
+        courses.add_prerequisite(&String::from("CS 350"), &String::from("MATH 2"));
+
+        assert_eq!(
+            sorted(courses.get_dependents("MATH 2")),
+            vec!["CS 105", "CS 201", "CS 301", "CS 350"]
+        );
+        assert_eq!(
+            sorted(courses.get_dependents("CS 101")),
+            vec!["CS 201", "CS 301", "CS 350"]
+        );
+        assert!(courses.get_dependents("CS 301").is_empty());
+    }
+
+    #[test]
+    fn test_get_prerequisite_chains() {
+        let mut courses: Courses = Courses::new();
+
+        for course_name in [
+            "MATH 1", "MATH 2", "CS 101", "CS 105", "CS 201", "CS 301", "CS 350", "ART 1",
+        ] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite(&String::from("MATH 2"), &String::from("MATH 1"));
+        courses.add_prerequisite(&String::from("CS 105"), &String::from("MATH 2"));
+        courses.add_prerequisite_choice(
+            &String::from("CS 201"),
+            &[String::from("CS 101"), String::from("CS 105")],
+        );
+        courses.add_prerequisite(&String::from("CS 301"), &String::from("CS 201"));
+        courses.add_prerequisite(&String::from("CS 350"), &String::from("CS 201"));
+        courses.add_prerequisite(&String::from("CS 350"), &String::from("MATH 2"));
+
+        assert_eq!(
+            courses.get_shortest_prerequisite_chain("CS 350"),
+            Some(vec![
+                String::from("CS 101"),
+                String::from("CS 201"),
+                String::from("CS 350")
+            ])
+        );
+        assert_eq!(
+            courses.get_longest_prerequisite_chain("CS 105"),
+            Some(vec![
+                String::from("MATH 1"),
+                String::from("MATH 2"),
+                String::from("CS 105")
+            ])
+        );
+        // CS 201 can follow CS 101 instead of the longer chain to CS 105.
+        assert_eq!(
+            courses.get_longest_prerequisite_chain("CS 301"),
+            Some(vec![
+                String::from("CS 101"),
+                String::from("CS 201"),
+                String::from("CS 301")
+            ])
+        );
+        assert_eq!(
+            courses
+                .get_longest_prerequisite_chain("CS 350")
+                .map(|x| x.len()),
+            Some(3)
+        );
+        assert_eq!(
+            courses.get_shortest_prerequisite_chain("ART 1"),
+            Some(vec![String::from("ART 1")])
+        );
+        assert_eq!(courses.get_longest_prerequisite_chain("CS 999"), None);
+    }
+
+    #[test]
+    fn test_prerequisite_chains_with_loop() {
+        let mut courses: Courses = Courses::new();
+        courses.add_course(Course::new(String::from("A"), 4));
+        courses.add_course(Course::new(String::from("B"), 4));
+        courses.add_prerequisite(&String::from("A"), &String::from("B"));
+        courses.add_prerequisite(&String::from("B"), &String::from("A"));
+
+        assert_eq!(courses.get_shortest_prerequisite_chain("A"), None);
+        assert_eq!(
+            courses.get_longest_prerequisite_chain("A"),
+            Some(vec![String::from("B"), String::from("A")])
+        );
+        assert_eq!(sorted(courses.get_all_prerequisites("A")), vec!["B"]);
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use crate::{CourseMove, Credits, Term, TracedTerm};
+
+/// A term whose units differ between the plans. A term missing from a plan
+/// has no units in it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnitChange {
+    pub term: TracedTerm,
+    pub old_units: Credits,
+    pub new_units: Credits,
+}
+
+/// What changes going from one plan to another, with courses sorted by
+/// name. Courses taken more than once are compared instance by instance.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlanDiff {
+    /// Courses only the new plan has, with the term it plans them in.
+    pub added: Vec<(String, TracedTerm)>,
+    /// Courses only the old plan has, with the term it planned them in.
+    pub removed: Vec<(String, TracedTerm)>,
+    pub moved: Vec<CourseMove>,
+    /// Terms whose units change, in the order they come in.
+    pub unit_changes: Vec<UnitChange>,
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.unit_changes.is_empty()
+    }
+}
+
+impl fmt::Display for PlanDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        for (course_name, term) in &self.added {
+            writeln!(f, "+ {} in {}", course_name, term)?;
+        }
+        for (course_name, term) in &self.removed {
+            writeln!(f, "- {} from {}", course_name, term)?;
+        }
+        for course_move in &self.moved {
+            writeln!(
+                f,
+                "~ {} moves from {} to {}",
+                course_move.course, course_move.from, course_move.to
+            )?;
+        }
+        for unit_change in &self.unit_changes {
+            writeln!(
+                f,
+                "  {}: {} units, was {}",
+                unit_change.term, unit_change.new_units, unit_change.old_units
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The year and term type of a term, or its position in the plan.
+type TermKey = (usize, usize);
+
+/// Gets the key a term is told apart from the other terms of the plans by.
+/// Years begin with Fall, so the year and then the term type puts terms in
+/// the order they come in. Plans whose years are not set have the same key
+/// for more than one term, so their terms are keyed by position instead.
+fn get_term_key(index: usize, term: &Term, has_years: bool) -> TermKey {
+    match has_years {
+        true => (term.year as usize, term.term_type.clone() as usize),
+        false => (index, 0),
+    }
+}
+
+/// Gets the terms each course is planned in, in the order of the plan,
+/// along with the key of each term.
+fn get_course_terms(
+    terms: &[Term],
+    has_years: bool,
+) -> BTreeMap<String, Vec<(TermKey, TracedTerm)>> {
+    let mut course_terms: BTreeMap<String, Vec<(TermKey, TracedTerm)>> = BTreeMap::new();
+
+    for (index, term) in terms.iter().enumerate() {
+        for (course_name, _) in &term.courses {
+            course_terms.entry(course_name.clone()).or_default().push((
+                get_term_key(index, term, has_years),
+                TracedTerm {
+                    term: term.term_type.clone(),
+                    year: term.year,
+                },
+            ));
+        }
+    }
+
+    course_terms
+}
+
+/// Checks whether no two terms of the plan share a year and term type.
+fn has_distinct_terms(terms: &[Term]) -> bool {
+    let mut seen_terms: HashSet<(u16, usize)> = HashSet::new();
+
+    terms
+        .iter()
+        .all(|x| seen_terms.insert((x.year, x.term_type.clone() as usize)))
+}
+
+/// Compares two plans, finding the courses the new plan adds, removes or
+/// moves to another term, and the terms that end up with more or fewer
+/// units. Terms are told apart by their year and term type, or by their
+/// position in the plans when either plan repeats a year and term type, as
+/// plans without years set do.
+pub fn diff_plans(old_terms: &[Term], new_terms: &[Term]) -> PlanDiff {
+    let has_years: bool = has_distinct_terms(old_terms) && has_distinct_terms(new_terms);
+    let old_course_terms = get_course_terms(old_terms, has_years);
+    let mut new_course_terms = get_course_terms(new_terms, has_years);
+    let mut diff = PlanDiff::default();
+
+    for (course_name, old_instances) in old_course_terms {
+        let new_instances: Vec<(TermKey, TracedTerm)> =
+            new_course_terms.remove(&course_name).unwrap_or_default();
+
+        for (old_instance, new_instance) in old_instances.iter().zip(&new_instances) {
+            if old_instance.0 != new_instance.0 {
+                diff.moved.push(CourseMove {
+                    course: course_name.clone(),
+                    from: old_instance.1.clone(),
+                    to: new_instance.1.clone(),
+                });
+            }
+        }
+
+        for old_instance in old_instances.iter().skip(new_instances.len()) {
+            diff.removed
+                .push((course_name.clone(), old_instance.1.clone()));
+        }
+        for new_instance in new_instances.iter().skip(old_instances.len()) {
+            diff.added
+                .push((course_name.clone(), new_instance.1.clone()));
+        }
+    }
+
+    for (course_name, new_instances) in new_course_terms {
+        for new_instance in new_instances {
+            diff.added.push((course_name.clone(), new_instance.1));
+        }
+    }
+    diff.added.sort_by(|x, y| x.0.cmp(&y.0));
+
+    let mut term_units: BTreeMap<TermKey, UnitChange> = BTreeMap::new();
+    for (index, term, is_old) in old_terms
+        .iter()
+        .enumerate()
+        .map(|(index, x)| (index, x, true))
+        .chain(
+            new_terms
+                .iter()
+                .enumerate()
+                .map(|(index, x)| (index, x, false)),
+        )
+    {
+        let unit_change = term_units
+            .entry(get_term_key(index, term, has_years))
+            .or_insert(UnitChange {
+                term: TracedTerm {
+                    term: term.term_type.clone(),
+                    year: term.year,
+                },
+                old_units: Credits::ZERO,
+                new_units: Credits::ZERO,
+            });
+
+        match is_old {
+            true => unit_change.old_units = term.units,
+            false => unit_change.new_units = term.units,
+        }
+    }
+    diff.unit_changes = term_units
+        .into_values()
+        .filter(|x| x.old_units != x.new_units)
+        .collect();
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Course, TermType};
+
+    fn get_term(term_type: &TermType, year: u16, course_names: &[&str]) -> Term {
+        let mut term = Term::new(term_type, 16);
+        term.set_year(year);
+
+        for course_name in course_names {
+            term.add(&Course::new(course_name.to_string(), 4)).unwrap();
+        }
+
+        term
+    }
+
+    #[test]
+    fn test_diff_plans() {
+        let old_terms: Vec<Term> = vec![
+            get_term(&TermType::Fall, 1, &["CS 101", "ART 1"]),
+            get_term(&TermType::Winter, 1, &["CS 201"]),
+        ];
+        let new_terms: Vec<Term> = vec![
+            get_term(&TermType::Fall, 1, &["CS 101"]),
+            get_term(&TermType::Spring, 1, &["CS 201", "MATH 1"]),
+        ];
+
+        let diff: PlanDiff = diff_plans(&old_terms, &new_terms);
+        assert_eq!(
+            diff.added,
+            vec![(
+                String::from("MATH 1"),
+                TracedTerm {
+                    term: TermType::Spring,
+                    year: 1
+                }
+            )]
+        );
+        assert_eq!(
+            diff.removed,
+            vec![(
+                String::from("ART 1"),
+                TracedTerm {
+                    term: TermType::Fall,
+                    year: 1
+                }
+            )]
+        );
+        assert_eq!(
+            diff.moved,
+            vec![CourseMove {
+                course: String::from("CS 201"),
+                from: TracedTerm {
+                    term: TermType::Winter,
+                    year: 1
+                },
+                to: TracedTerm {
+                    term: TermType::Spring,
+                    year: 1
+                },
+            }]
+        );
+
+        assert_eq!(
+            diff.unit_changes
+                .iter()
+                .map(|x| (x.term.to_string(), x.old_units, x.new_units))
+                .collect::<Vec<(String, Credits, Credits)>>(),
+            vec![
+                (
+                    String::from("Fall, year 1"),
+                    Credits::from(8),
+                    Credits::from(4)
+                ),
+                (
+                    String::from("Winter, year 1"),
+                    Credits::from(4),
+                    Credits::ZERO
+                ),
+                (
+                    String::from("Spring, year 1"),
+                    Credits::ZERO,
+                    Credits::from(8)
+                ),
+            ]
+        );
+
+        assert_eq!(
+            diff.to_string(),
+            "+ MATH 1 in Spring, year 1\n\
+             - ART 1 from Fall, year 1\n\
+             ~ CS 201 moves from Winter, year 1 to Spring, year 1\n\
+             \x20 Fall, year 1: 4 units, was 8\n\
+             \x20 Winter, year 1: 0 units, was 4\n\
+             \x20 Spring, year 1: 8 units, was 0\n"
+        );
+
+        let same_diff: PlanDiff = diff_plans(&old_terms, &old_terms);
+        assert!(same_diff.is_empty());
+        assert_eq!(same_diff.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn test_diff_plans_without_years() {
+        let old_terms: Vec<Term> = vec![
+            get_term(&TermType::Fall, 1, &["CS 101"]),
+            get_term(&TermType::Fall, 1, &["CS 201", "ART 1"]),
+        ];
+        let new_terms: Vec<Term> = vec![
+            get_term(&TermType::Fall, 1, &["CS 101", "ART 1"]),
+            get_term(&TermType::Fall, 1, &["CS 201"]),
+        ];
+
+        let diff: PlanDiff = diff_plans(&old_terms, &new_terms);
+        assert_eq!(
+            diff.unit_changes
+                .iter()
+                .map(|x| (x.old_units, x.new_units))
+                .collect::<Vec<(Credits, Credits)>>(),
+            vec![
+                (Credits::from(4), Credits::from(8)),
+                (Credits::from(8), Credits::from(4))
+            ]
+        );
+        assert_eq!(
+            diff.moved,
+            vec![CourseMove {
+                course: String::from("ART 1"),
+                from: TracedTerm {
+                    term: TermType::Fall,
+                    year: 1
+                },
+                to: TracedTerm {
+                    term: TermType::Fall,
+                    year: 1
+                },
+            }]
+        );
+    }
+}
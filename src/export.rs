@@ -0,0 +1,15 @@
+//! Writing plans and catalogs out in formats that other tools read.
+
+mod graph;
+mod ics;
+#[cfg(feature = "json")]
+mod json;
+mod report;
+
+pub use self::graph::{prerequisites_to_dot, prerequisites_to_mermaid, GraphFocus, GraphOptions};
+pub use self::ics::{
+    plan_to_ics, CalendarDate, MissingTermDates, ParseCalendarDateError, TermCalendar,
+};
+#[cfg(feature = "json")]
+pub use self::json::{plan_to_json, PLAN_JSON_SCHEMA};
+pub use self::report::{plan_to_html, plan_to_latex, plan_to_markdown};
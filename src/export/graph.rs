@@ -0,0 +1,387 @@
+//! Graphviz DOT and Mermaid drawings of the prerequisite graph of a catalog,
+//! with each group of concurrent courses drawn as a cluster.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{Courses, Term, TermType};
+
+/// Which courses around a chosen course to draw.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphFocus {
+    /// The course and every course it depends on.
+    Ancestors,
+    /// The course and every course that depends on it.
+    Descendants,
+    Both,
+}
+
+/// What to draw of the prerequisite graph. By default every course is drawn
+/// without colors.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphOptions {
+    focus: Option<(String, GraphFocus)>,
+    planned_terms: HashMap<String, (usize, TermType)>,
+}
+
+impl GraphOptions {
+    pub fn new() -> GraphOptions {
+        GraphOptions {
+            focus: None,
+            planned_terms: HashMap::new(),
+        }
+    }
+
+    /// Only draws the course along with its ancestors, descendants or both.
+    pub fn focus_on(&mut self, course: &str, focus: GraphFocus) -> &mut GraphOptions {
+        self.focus = Some((course.to_string(), focus));
+        self
+    }
+
+    /// Colors each course by the type of term a plan places it in, also
+    /// labelling it with the number of that term.
+    pub fn color_by_terms(&mut self, terms: &[Term]) -> &mut GraphOptions {
+        self.planned_terms.clear();
+
+        for (index, term) in terms.iter().enumerate() {
+            for course in term.courses() {
+                self.planned_terms
+                    .entry(course.0.clone())
+                    .or_insert((index, term.term_type().clone()));
+            }
+        }
+
+        self
+    }
+}
+
+fn term_color(term: &TermType) -> &'static str {
+    match term {
+        TermType::Fall => "#f4a261",
+        TermType::Winter => "#8ecae6",
+        TermType::Spring => "#95d5b2",
+        TermType::Summer => "#ffe66d",
+    }
+}
+
+/// A prerequisite edge, where a choice edge is one option of a prerequisite
+/// choice.
+struct Edge {
+    from: String,
+    to: String,
+    is_choice: bool,
+}
+
+/// The courses, concurrency groups and edges left to draw once the options
+/// are applied, each in a stable order.
+struct PrerequisiteGraph {
+    courses: Vec<String>,
+    groups: Vec<Vec<String>>,
+    edges: Vec<Edge>,
+}
+
+impl PrerequisiteGraph {
+    fn new(courses: &Courses, options: &GraphOptions) -> PrerequisiteGraph {
+        let mut edges: Vec<Edge> = Vec::new();
+
+        for (course, prerequisites) in courses.prerequisites.iter_all() {
+            for prerequisite in prerequisites {
+                edges.push(Edge {
+                    from: prerequisite.clone(),
+                    to: course.clone(),
+                    is_choice: false,
+                });
+            }
+        }
+
+        for (course, choices) in courses.prerequisite_choices.iter_all() {
+            for option in choices.iter().flatten() {
+                edges.push(Edge {
+                    from: option.clone(),
+                    to: course.clone(),
+                    is_choice: true,
+                });
+            }
+        }
+
+        let included_courses: HashSet<String> = match &options.focus {
+            Some((course, focus)) => {
+                let mut included_courses: HashSet<String> = HashSet::new();
+                included_courses.insert(course.clone());
+
+                if *focus != GraphFocus::Descendants {
+                    included_courses.extend(courses.get_all_prerequisites(course));
+                }
+
+                if *focus != GraphFocus::Ancestors {
+                    included_courses.extend(courses.get_dependents(course));
+                }
+
+                included_courses
+            }
+            None => courses.master_list.keys().cloned().collect(),
+        };
+
+        edges.retain(|x| included_courses.contains(&x.from) && included_courses.contains(&x.to));
+        edges.sort_by(|a, b| (&a.from, &a.to, a.is_choice).cmp(&(&b.from, &b.to, b.is_choice)));
+        edges.dedup_by(|a, b| a.from == b.from && a.to == b.to && a.is_choice == b.is_choice);
+
+        let mut groups: BTreeSet<Vec<String>> = BTreeSet::new();
+        for course in &included_courses {
+            if let Some((concurrents, _)) = courses.get_concurrents_for(course) {
+                let mut group: Vec<String> = concurrents
+                    .into_iter()
+                    .filter(|x| included_courses.contains(x))
+                    .collect();
+                group.sort();
+
+                if group.len() > 1 {
+                    groups.insert(group);
+                }
+            }
+        }
+
+        let mut sorted_courses: Vec<String> = included_courses.into_iter().collect();
+        sorted_courses.sort();
+
+        PrerequisiteGraph {
+            courses: sorted_courses,
+            groups: groups.into_iter().collect(),
+            edges,
+        }
+    }
+
+    fn is_grouped(&self, course: &String) -> bool {
+        self.groups.iter().any(|x| x.contains(course))
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn dot_node(course: &String, options: &GraphOptions) -> String {
+    match options.planned_terms.get(course) {
+        Some((index, term)) => format!(
+            "\"{}\" [label=\"{}\\n{} (term {})\", style=filled, fillcolor=\"{}\"];",
+            escape_dot(course),
+            escape_dot(course),
+            term,
+            index + 1,
+            term_color(term)
+        ),
+        None => format!("\"{}\";", escape_dot(course)),
+    }
+}
+
+/// Draws the prerequisite graph in Graphviz DOT, with an arrow from each
+/// prerequisite to the course that needs it. Options of a prerequisite
+/// choice are drawn as dashed arrows.
+pub fn prerequisites_to_dot(courses: &Courses, options: &GraphOptions) -> String {
+    let graph = PrerequisiteGraph::new(courses, options);
+    let mut dot = String::from("digraph prerequisites {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for (index, group) in graph.groups.iter().enumerate() {
+        dot.push_str(&format!(
+            "    subgraph cluster_{} {{\n        label=\"Taken together\";\n        style=dashed;\n",
+            index
+        ));
+
+        for course in group {
+            dot.push_str(&format!("        {}\n", dot_node(course, options)));
+        }
+
+        dot.push_str("    }\n");
+    }
+
+    for course in graph.courses.iter().filter(|x| !graph.is_grouped(x)) {
+        dot.push_str(&format!("    {}\n", dot_node(course, options)));
+    }
+
+    for edge in &graph.edges {
+        let attributes = match edge.is_choice {
+            true => " [style=dashed, label=\"or\"]",
+            false => "",
+        };
+
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\"{};\n",
+            escape_dot(&edge.from),
+            escape_dot(&edge.to),
+            attributes
+        ));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+/// Draws the prerequisite graph as a Mermaid flowchart, in the same way as
+/// `prerequisites_to_dot`.
+pub fn prerequisites_to_mermaid(courses: &Courses, options: &GraphOptions) -> String {
+    let graph = PrerequisiteGraph::new(courses, options);
+    let node_ids: HashMap<&String, String> = graph
+        .courses
+        .iter()
+        .enumerate()
+        .map(|(index, course)| (course, format!("n{}", index)))
+        .collect();
+
+    let mermaid_node = |course: &String| -> String {
+        let label: String = match options.planned_terms.get(course) {
+            Some((index, term)) => format!(
+                "{}<br/>{} (term {})",
+                escape_mermaid(course),
+                term,
+                index + 1
+            ),
+            None => escape_mermaid(course),
+        };
+
+        format!("{}[\"{}\"]", node_ids[course], label)
+    };
+
+    let mut mermaid = String::from("flowchart LR\n");
+
+    for (index, group) in graph.groups.iter().enumerate() {
+        mermaid.push_str(&format!(
+            "    subgraph group{} [\"Taken together\"]\n",
+            index
+        ));
+
+        for course in group {
+            mermaid.push_str(&format!("        {}\n", mermaid_node(course)));
+        }
+
+        mermaid.push_str("    end\n");
+    }
+
+    for course in graph.courses.iter().filter(|x| !graph.is_grouped(x)) {
+        mermaid.push_str(&format!("    {}\n", mermaid_node(course)));
+    }
+
+    for edge in &graph.edges {
+        let arrow = match edge.is_choice {
+            true => "-. or .->",
+            false => "-->",
+        };
+
+        mermaid.push_str(&format!(
+            "    {} {} {}\n",
+            node_ids[&edge.from], arrow, node_ids[&edge.to]
+        ));
+    }
+
+    for course in &graph.courses {
+        if let Some((_, term)) = options.planned_terms.get(course) {
+            mermaid.push_str(&format!(
+                "    style {} fill:{}\n",
+                node_ids[course],
+                term_color(term)
+            ));
+        }
+    }
+
+    mermaid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+
+    #[test]
+    fn test_prerequisites_to_dot() {
+        let mut courses = Courses::new();
+
+        for course_name in ["CS 101", "CS 105", "CS 201", "CS 201L", "CS 301", "ART 1"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite_choice(
+            &String::from("CS 201"),
+            &[String::from("CS 101"), String::from("CS 105")],
+        );
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_prerequisite(&String::from("CS 301"), &String::from("CS 201"));
+
+        let dot: String = prerequisites_to_dot(&courses, &GraphOptions::new());
+
+        assert!(dot.starts_with("digraph prerequisites {\n"));
+        assert!(dot.contains(
+            "    subgraph cluster_0 {\n        label=\"Taken together\";\n        style=dashed;\n        \"CS 201\";\n        \"CS 201L\";\n    }\n"
+        ));
+        assert!(dot.contains("    \"ART 1\";\n"));
+        assert!(dot.contains("    \"CS 201\" -> \"CS 301\";\n"));
+        assert!(dot.contains("    \"CS 101\" -> \"CS 201\" [style=dashed, label=\"or\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_prerequisites_to_mermaid_colored() {
+        let mut courses = Courses::new();
+
+        for course_name in ["CS 101", "CS 105", "CS 201", "CS 201L", "CS 301", "ART 1"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite_choice(
+            &String::from("CS 201"),
+            &[String::from("CS 101"), String::from("CS 105")],
+        );
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_prerequisite(&String::from("CS 301"), &String::from("CS 201"));
+
+        let completed_terms: Vec<Term> = courses.get_terms([12, 12, 12, 12]).unwrap();
+
+        let mut options = GraphOptions::new();
+        options.color_by_terms(&completed_terms);
+
+        let mermaid: String = prerequisites_to_mermaid(&courses, &options);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("    subgraph group0 [\"Taken together\"]\n"));
+        assert!(mermaid.contains("    n5[\"CS 301<br/>Spring (term 3)\"]\n"));
+        assert!(mermaid.contains("    n3 --> n5\n"));
+        assert!(mermaid.contains("    n1 -. or .-> n3\n"));
+        assert!(mermaid.contains("    style n5 fill:#95d5b2\n"));
+    }
+
+    #[test]
+    fn test_graph_focus() {
+        let mut courses = Courses::new();
+
+        for course_name in ["CS 101", "CS 105", "CS 201", "CS 201L", "CS 301", "ART 1"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite_choice(
+            &String::from("CS 201"),
+            &[String::from("CS 101"), String::from("CS 105")],
+        );
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_prerequisite(&String::from("CS 301"), &String::from("CS 201"));
+
+        let mut options = GraphOptions::new();
+        options.focus_on("CS 201", GraphFocus::Ancestors);
+
+        let dot: String = prerequisites_to_dot(&courses, &options);
+        assert!(dot.contains("\"CS 101\""));
+        assert!(!dot.contains("\"CS 301\""));
+        assert!(!dot.contains("\"ART 1\""));
+
+        options.focus_on("CS 105", GraphFocus::Descendants);
+
+        let dot: String = prerequisites_to_dot(&courses, &options);
+        assert!(dot.contains("\"CS 301\""));
+        assert!(!dot.contains("\"CS 101\""));
+    }
+}
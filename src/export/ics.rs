@@ -0,0 +1,414 @@
+//! An iCalendar (RFC 5545) file of a plan, with one all-day event spanning
+//! each term that lists the courses taken in it.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Term, TermType};
+
+/// A day of the Gregorian calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl CalendarDate {
+    /// Creates the date, or gets None when the month or day does not exist.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<CalendarDate> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        Some(CalendarDate { year, month, day })
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Gets the same day in another year, moving February 29th to the 28th
+    /// when that year is not a leap year.
+    pub fn with_year(&self, year: i32) -> CalendarDate {
+        CalendarDate {
+            year,
+            month: self.month,
+            day: self.day.min(days_in_month(year, self.month)),
+        }
+    }
+
+    pub fn next_day(&self) -> CalendarDate {
+        if self.day < days_in_month(self.year, self.month) {
+            CalendarDate {
+                day: self.day + 1,
+                ..*self
+            }
+        } else if self.month < 12 {
+            CalendarDate {
+                month: self.month + 1,
+                day: 1,
+                ..*self
+            }
+        } else {
+            CalendarDate {
+                year: self.year + 1,
+                month: 1,
+                day: 1,
+            }
+        }
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseCalendarDateError(pub String);
+
+impl fmt::Display for ParseCalendarDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a date, expected YYYY-MM-DD", self.0)
+    }
+}
+
+impl Error for ParseCalendarDateError {}
+
+impl FromStr for CalendarDate {
+    type Err = ParseCalendarDateError;
+
+    /// Reads a date written as YYYY-MM-DD.
+    fn from_str(text: &str) -> Result<CalendarDate, ParseCalendarDateError> {
+        let error = || ParseCalendarDateError(text.to_string());
+
+        let parts: Vec<&str> = text.trim().split('-').collect();
+        match parts.as_slice() {
+            [year, month, day] => CalendarDate::new(
+                year.parse().map_err(|_| error())?,
+                month.parse().map_err(|_| error())?,
+                day.parse().map_err(|_| error())?,
+            )
+            .ok_or_else(error),
+            _ => Err(error()),
+        }
+    }
+}
+
+/// The first and last day of each term, by term type and the year of the
+/// plan it falls in, as given by `Term::year`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TermCalendar {
+    dates: HashMap<(TermType, u16), (CalendarDate, CalendarDate)>,
+}
+
+impl TermCalendar {
+    pub fn new() -> TermCalendar {
+        TermCalendar {
+            dates: HashMap::new(),
+        }
+    }
+
+    pub fn set_dates(
+        &mut self,
+        term: &TermType,
+        year: u16,
+        first_day: CalendarDate,
+        last_day: CalendarDate,
+    ) -> &mut TermCalendar {
+        self.dates
+            .insert((term.clone(), year), (first_day, last_day));
+        self
+    }
+
+    /// Sets the dates of a term for the first `years_count` years of a plan,
+    /// starting from the dates it has in the first year and moving them one
+    /// calendar year later for each year after.
+    pub fn set_yearly_dates(
+        &mut self,
+        term: &TermType,
+        first_day: CalendarDate,
+        last_day: CalendarDate,
+        years_count: u16,
+    ) -> &mut TermCalendar {
+        for year in 1..=years_count {
+            let offset = i32::from(year - 1);
+
+            self.set_dates(
+                term,
+                year,
+                first_day.with_year(first_day.year + offset),
+                last_day.with_year(last_day.year + offset),
+            );
+        }
+
+        self
+    }
+
+    pub fn get_dates(&self, term: &TermType, year: u16) -> Option<(CalendarDate, CalendarDate)> {
+        self.dates.get(&(term.clone(), year)).copied()
+    }
+}
+
+/// A term of the plan that the calendar has no dates for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MissingTermDates {
+    pub term: TermType,
+    pub year: u16,
+}
+
+impl fmt::Display for MissingTermDates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no dates are set for {} of year {} of the plan",
+            self.term, self.year
+        )
+    }
+}
+
+impl Error for MissingTermDates {}
+
+/// Escapes text for use in a property value.
+fn escape_text(text: &str) -> String {
+    let mut escaped_text = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '\\' => escaped_text.push_str("\\\\"),
+            ';' => escaped_text.push_str("\\;"),
+            ',' => escaped_text.push_str("\\,"),
+            '\n' => escaped_text.push_str("\\n"),
+            _ => escaped_text.push(character),
+        }
+    }
+
+    escaped_text
+}
+
+/// Adds a content line to the calendar, folding it so that no line is
+/// longer than 75 bytes, as RFC 5545 asks.
+fn push_line(calendar: &mut String, line: &str) {
+    let mut line_length = 0;
+
+    for character in line.chars() {
+        if line_length + character.len_utf8() > 75 {
+            calendar.push_str("\r\n ");
+            line_length = 1;
+        }
+
+        calendar.push(character);
+        line_length += character.len_utf8();
+    }
+
+    calendar.push_str("\r\n");
+}
+
+fn format_date(date: &CalendarDate) -> String {
+    format!("{:04}{:02}{:02}", date.year, date.month, date.day)
+}
+
+/// Formats a time as a UTC date-time, treating times before 1970 as 1970.
+fn format_timestamp(time: SystemTime) -> String {
+    let seconds: u64 = time.duration_since(UNIX_EPOCH).map_or(0, |x| x.as_secs());
+
+    // Converts days since 1970 to a date by counting from 0000-03-01, so
+    // that leap days fall at the end of each 400 year era.
+    let days: i64 = (seconds / 86_400) as i64 + 719_468;
+    let era: i64 = days.div_euclid(146_097);
+    let day_of_era: i64 = days - era * 146_097;
+    let year_of_era: i64 =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: i64 = (5 * day_of_year + 2) / 153;
+    let day: i64 = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month: i64 = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year: i64 = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let time_of_day: u64 = seconds % 86_400;
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        time_of_day / 3_600,
+        time_of_day / 60 % 60,
+        time_of_day % 60
+    )
+}
+
+/// Writes the terms of a plan as an iCalendar file with an all-day event
+/// spanning each term, failing when the calendar lacks the dates of a term.
+/// Each event is stamped with the time the plan was exported at, which is
+/// usually `SystemTime::now()`.
+pub fn plan_to_ics(
+    terms: &[Term],
+    calendar: &TermCalendar,
+    exported_at: SystemTime,
+) -> Result<String, MissingTermDates> {
+    let timestamp: String = format_timestamp(exported_at);
+
+    let mut ics = String::new();
+
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, "PRODID:-//cle-rust//Course Plan//EN");
+    push_line(&mut ics, "CALSCALE:GREGORIAN");
+
+    for (index, term) in terms.iter().enumerate() {
+        let (first_day, last_day) = calendar
+            .get_dates(term.term_type(), term.year())
+            .ok_or_else(|| MissingTermDates {
+                term: term.term_type().clone(),
+                year: term.year(),
+            })?;
+
+        let description: Vec<String> = term
+            .courses()
+            .iter()
+            .map(|x| format!("{} ({} units)", x.0, x.1))
+            .collect();
+
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(
+            &mut ics,
+            &format!(
+                "UID:term-{}-{}-{}@cle-rust",
+                index + 1,
+                term.term_type().to_string().to_lowercase(),
+                first_day.year
+            ),
+        );
+        push_line(&mut ics, &format!("DTSTAMP:{}", timestamp));
+        push_line(
+            &mut ics,
+            &format!("DTSTART;VALUE=DATE:{}", format_date(&first_day)),
+        );
+        // The end of an all-day event is the day after it ends.
+        push_line(
+            &mut ics,
+            &format!("DTEND;VALUE=DATE:{}", format_date(&last_day.next_day())),
+        );
+        push_line(
+            &mut ics,
+            &format!(
+                "SUMMARY:{}",
+                escape_text(&format!(
+                    "{} {}: {} units",
+                    term.term_type(),
+                    first_day.year,
+                    term.units()
+                ))
+            ),
+        );
+        push_line(
+            &mut ics,
+            &format!("DESCRIPTION:{}", escape_text(&description.join("\n"))),
+        );
+        push_line(&mut ics, "TRANSP:TRANSPARENT");
+        push_line(&mut ics, "END:VEVENT");
+    }
+
+    push_line(&mut ics, "END:VCALENDAR");
+
+    Ok(ics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Course, Courses};
+    use std::time::Duration;
+
+    fn date(text: &str) -> CalendarDate {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_calendar_date() {
+        assert_eq!(
+            CalendarDate::new(2024, 2, 29).unwrap().to_string(),
+            "2024-02-29"
+        );
+        assert_eq!(CalendarDate::new(2025, 2, 29), None);
+        assert_eq!(CalendarDate::new(2025, 13, 1), None);
+        assert_eq!(date("2025-12-31").next_day(), date("2026-01-01"));
+        assert_eq!(date("2024-02-28").next_day(), date("2024-02-29"));
+        assert_eq!(date("2024-02-29").with_year(2025), date("2025-02-28"));
+        assert!("2025/09/22".parse::<CalendarDate>().is_err());
+    }
+
+    #[test]
+    fn test_plan_to_ics() {
+        let mut courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 101"), 4));
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("Art, Design; Media"), 4));
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+
+        let mut calendar = TermCalendar::new();
+        calendar
+            .set_yearly_dates(&TermType::Fall, date("2025-09-22"), date("2025-12-12"), 4)
+            .set_yearly_dates(&TermType::Winter, date("2026-01-05"), date("2026-03-20"), 4);
+
+        let exported_at: SystemTime = UNIX_EPOCH + Duration::from_secs(1_709_210_096);
+        let ics: String = plan_to_ics(&completed_terms, &calendar, exported_at).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250922\r\nDTEND;VALUE=DATE:20251213\r\n"));
+        assert!(ics.contains("SUMMARY:Winter 2026: 4 units\r\n"));
+        assert_eq!(ics.matches("DTSTAMP:20240229T123456Z\r\n").count(), 2);
+        assert!(ics.contains("Art\\, Design\\; Media (4 units)"));
+        assert!(ics.lines().all(|x| x.len() <= 75));
+    }
+
+    #[test]
+    fn test_plan_to_ics_missing_dates() {
+        let mut courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 101"), 4));
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+
+        assert_eq!(
+            plan_to_ics(&completed_terms, &TermCalendar::new(), UNIX_EPOCH),
+            Err(MissingTermDates {
+                term: TermType::Fall,
+                year: 1
+            })
+        );
+    }
+}
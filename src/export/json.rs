@@ -0,0 +1,198 @@
+//! A JSON document of a plan for web frontends, following the schema in
+//! `schema/plan.schema.json`.
+
+use serde::Serialize;
+
+use crate::{Courses, Credits, Term, TermType};
+
+/// The JSON Schema that documents written by `plan_to_json` follow.
+pub const PLAN_JSON_SCHEMA: &str = include_str!("../../schema/plan.schema.json");
+
+const PLAN_JSON_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct PlanDocument<'a> {
+    version: u32,
+    total_units: Credits,
+    terms: Vec<TermEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct TermEntry<'a> {
+    #[serde(rename = "type")]
+    term_type: &'a TermType,
+    year: u16,
+    units: Credits,
+    unit_limit: Credits,
+    courses: Vec<CourseEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct CourseEntry<'a> {
+    name: &'a str,
+    units: Credits,
+    credits: Credits,
+    max_credits: Credits,
+    prerequisites: Vec<String>,
+    prerequisite_choices: Vec<Vec<String>>,
+    concurrent: Vec<String>,
+}
+
+fn to_course_entry<'a>(courses: &Courses, name: &'a String, units: Credits) -> CourseEntry<'a> {
+    let (credits, max_credits) = match courses.master_list.get(name) {
+        Some(x) => (x.credits, x.max_credits),
+        None => (units, units),
+    };
+
+    let mut prerequisites: Vec<String> = courses
+        .get_prerequisites(name)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    prerequisites.sort();
+
+    let prerequisite_choices: Vec<Vec<String>> =
+        courses.get_prerequisite_choices(name).unwrap_or_default();
+
+    let mut concurrent: Vec<String> = courses
+        .get_concurrents_for(name)
+        .map(|x| x.0)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|x| x != name)
+        .collect();
+    concurrent.sort();
+
+    CourseEntry {
+        name,
+        units,
+        credits,
+        max_credits,
+        prerequisites,
+        prerequisite_choices,
+        concurrent,
+    }
+}
+
+/// Writes the terms of a plan as a JSON document, describing each course
+/// with the details the catalog holds for it. Courses that the catalog does
+/// not list are described by the units they are taken for alone.
+pub fn plan_to_json(courses: &Courses, terms: &[Term]) -> String {
+    let term_entries: Vec<TermEntry> = terms
+        .iter()
+        .map(|term| TermEntry {
+            term_type: term.term_type(),
+            year: term.year(),
+            units: term.units(),
+            unit_limit: term.unit_limit(),
+            courses: term
+                .courses()
+                .iter()
+                .map(|x| to_course_entry(courses, &x.0, x.1))
+                .collect(),
+        })
+        .collect();
+
+    let document = PlanDocument {
+        version: PLAN_JSON_VERSION,
+        total_units: Credits::checked_sum(terms.iter().map(|x| x.units())).unwrap_or(Credits::MAX),
+        terms: term_entries,
+    };
+
+    serde_json::to_string_pretty(&document).expect("Plan documents always serialize.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+    use serde_json::Value;
+
+    fn required_keys(schema: &Value) -> Vec<&str> {
+        schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|x| x.as_str().unwrap())
+            .collect()
+    }
+
+    fn object_keys(value: &Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|x| x.as_str())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_plan_to_json() {
+        let mut courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 101"), 4));
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(
+            String::from("CS 201L"),
+            Credits::from_hundredths(150),
+        ));
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+        let document: Value = serde_json::from_str(&plan_to_json(&courses, &completed_terms))
+            .expect("Plan export is not valid JSON.");
+
+        assert_eq!(document["version"], 1);
+        assert_eq!(document["total_units"], 9.5);
+
+        let terms = document["terms"].as_array().unwrap();
+        assert_eq!(terms.len(), 2);
+        assert_eq!(terms[0]["type"], "Fall");
+        assert_eq!(terms[0]["year"], 1);
+        assert_eq!(terms[0]["unit_limit"], 8);
+        assert_eq!(terms[1]["type"], "Winter");
+        assert_eq!(terms[1]["units"], 5.5);
+
+        let lab = terms[1]["courses"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|x| x["name"] == "CS 201L")
+            .unwrap();
+        assert_eq!(lab["units"], 1.5);
+        assert_eq!(lab["prerequisites"], serde_json::json!(["CS 101"]));
+        assert_eq!(lab["concurrent"], serde_json::json!(["CS 201"]));
+    }
+
+    #[test]
+    fn test_plan_json_follows_schema() {
+        let schema: Value =
+            serde_json::from_str(PLAN_JSON_SCHEMA).expect("Plan schema is not valid JSON.");
+
+        let mut courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 101"), 4));
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+        let document: Value =
+            serde_json::from_str(&plan_to_json(&courses, &completed_terms)).unwrap();
+
+        let mut plan_keys = required_keys(&schema);
+        plan_keys.sort();
+        assert_eq!(object_keys(&document), plan_keys);
+
+        let mut term_keys = required_keys(&schema["$defs"]["term"]);
+        term_keys.sort();
+        assert_eq!(object_keys(&document["terms"][0]), term_keys);
+
+        let mut course_keys = required_keys(&schema["$defs"]["course"]);
+        course_keys.sort();
+        assert_eq!(
+            object_keys(&document["terms"][0]["courses"][0]),
+            course_keys
+        );
+    }
+}
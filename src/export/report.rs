@@ -0,0 +1,273 @@
+//! Printable plan reports as a Markdown table, a standalone HTML page or a
+//! LaTeX table, listing every term along with a summary of the plan.
+
+use crate::{Credits, Term};
+
+/// A term of the report, with everything each format shows about it.
+struct ReportRow {
+    label: String,
+    courses: Vec<String>,
+    units: Credits,
+    unit_limit: Credits,
+    utilisation: f64,
+    cumulative_units: Credits,
+}
+
+struct Report {
+    rows: Vec<ReportRow>,
+    courses_count: usize,
+    total_units: Credits,
+}
+
+impl Report {
+    fn new(terms: &[Term]) -> Report {
+        let mut rows: Vec<ReportRow> = Vec::new();
+        let mut cumulative_units: Credits = Credits::ZERO;
+
+        for (index, term) in terms.iter().enumerate() {
+            cumulative_units = cumulative_units
+                .checked_add(term.units())
+                .unwrap_or(Credits::MAX);
+
+            rows.push(ReportRow {
+                label: format!("{}. {}, year {}", index + 1, term.term_type(), term.year()),
+                courses: term
+                    .courses()
+                    .iter()
+                    .map(|x| format!("{} ({})", x.0, x.1))
+                    .collect(),
+                units: term.units(),
+                unit_limit: term.unit_limit(),
+                utilisation: utilisation_of(term.units(), term.unit_limit()),
+                cumulative_units,
+            });
+        }
+
+        Report {
+            rows,
+            courses_count: terms.iter().map(|x| x.courses().len()).sum(),
+            total_units: cumulative_units,
+        }
+    }
+
+    fn average_utilisation(&self) -> f64 {
+        if self.rows.is_empty() {
+            return 0.0;
+        }
+
+        self.rows.iter().map(|x| x.utilisation).sum::<f64>() / self.rows.len() as f64
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} terms, {} courses, {} units total, {:.0}% average utilisation",
+            self.rows.len(),
+            self.courses_count,
+            self.total_units,
+            self.average_utilisation()
+        )
+    }
+}
+
+/// Gets the share of the unit limit that the units take up, as a percentage.
+fn utilisation_of(units: Credits, unit_limit: Credits) -> f64 {
+    if unit_limit == Credits::ZERO {
+        return 0.0;
+    }
+
+    units.as_f64() / unit_limit.as_f64() * 100.0
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Writes the plan as a Markdown table with a summary below it.
+pub fn plan_to_markdown(terms: &[Term]) -> String {
+    let report = Report::new(terms);
+    let mut markdown = String::from(
+        "# Course Plan\n\n\
+         | Term | Courses | Units | Limit | Utilisation | Cumulative units |\n\
+         | --- | --- | ---: | ---: | ---: | ---: |\n",
+    );
+
+    for row in &report.rows {
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} | {:.0}% | {} |\n",
+            row.label,
+            escape_markdown(&row.courses.join(", ")),
+            row.units,
+            row.unit_limit,
+            row.utilisation,
+            row.cumulative_units
+        ));
+    }
+
+    markdown.push_str(&format!("\n**Summary:** {}.\n", report.summary()));
+    markdown
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Writes the plan as a standalone HTML page, styled to print cleanly.
+pub fn plan_to_html(terms: &[Term]) -> String {
+    let report = Report::new(terms);
+    let mut html = String::from(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Course Plan</title>
+<style>
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #999; padding: 0.4em 0.6em; text-align: left; vertical-align: top; }
+td.number { text-align: right; }
+ul { margin: 0; padding-left: 1.2em; }
+@media print { body { margin: 0; } tr { page-break-inside: avoid; } }
+</style>
+</head>
+<body>
+<h1>Course Plan</h1>
+<table>
+<thead>
+<tr><th>Term</th><th>Courses</th><th>Units</th><th>Limit</th><th>Utilisation</th><th>Cumulative units</th></tr>
+</thead>
+<tbody>
+",
+    );
+
+    for row in &report.rows {
+        let course_items: String = row
+            .courses
+            .iter()
+            .map(|x| format!("<li>{}</li>", escape_html(x)))
+            .collect();
+
+        html.push_str(&format!(
+            "<tr><td>{}</td><td><ul>{}</ul></td><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"number\">{:.0}%</td><td class=\"number\">{}</td></tr>\n",
+            escape_html(&row.label),
+            course_items,
+            row.units,
+            row.unit_limit,
+            row.utilisation,
+            row.cumulative_units
+        ));
+    }
+
+    html.push_str(&format!(
+        "</tbody>\n</table>\n<p><strong>Summary:</strong> {}.</p>\n</body>\n</html>\n",
+        report.summary()
+    ));
+    html
+}
+
+fn escape_latex(text: &str) -> String {
+    let mut escaped_text = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '\\' => escaped_text.push_str("\\textbackslash{}"),
+            '~' => escaped_text.push_str("\\textasciitilde{}"),
+            '^' => escaped_text.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped_text.push('\\');
+                escaped_text.push(character);
+            }
+            _ => escaped_text.push(character),
+        }
+    }
+
+    escaped_text
+}
+
+/// Writes the plan as a LaTeX table, captioned with the summary, to include
+/// in a larger document.
+pub fn plan_to_latex(terms: &[Term]) -> String {
+    let report = Report::new(terms);
+    let mut latex = String::from(
+        "\\begin{table}[htbp]
+\\centering
+\\begin{tabular}{l p{0.4\\textwidth} r r r r}
+\\hline
+Term & Courses & Units & Limit & Utilisation & Cumulative units \\\\
+\\hline
+",
+    );
+
+    for row in &report.rows {
+        latex.push_str(&format!(
+            "{} & {} & {} & {} & {:.0}\\% & {} \\\\\n",
+            escape_latex(&row.label),
+            escape_latex(&row.courses.join(", ")),
+            row.units,
+            row.unit_limit,
+            row.utilisation,
+            row.cumulative_units
+        ));
+    }
+
+    latex.push_str(&format!(
+        "\\hline\n\\end{{tabular}}\n\\caption{{Course plan: {}.}}\n\\end{{table}}\n",
+        escape_latex(&report.summary())
+    ));
+    latex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Course, Courses};
+
+    fn get_terms() -> Vec<Term> {
+        let mut courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 101"), 4));
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("R&D_1"), 2));
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses.add_prerequisite(&String::from("R&D_1"), &String::from("CS 101"));
+
+        courses.get_terms([10, 10, 10, 10]).unwrap()
+    }
+
+    #[test]
+    fn test_plan_to_markdown() {
+        let markdown: String = plan_to_markdown(&get_terms());
+
+        assert!(markdown.contains("| 1. Fall, year 1 | CS 101 (4) | 4 | 10 | 40% | 4 |\n"));
+        assert!(markdown.contains("| 6 | 10 | 60% | 10 |\n"));
+        assert!(markdown.ends_with(
+            "**Summary:** 2 terms, 3 courses, 10 units total, 50% average utilisation.\n"
+        ));
+    }
+
+    #[test]
+    fn test_plan_to_html() {
+        let html: String = plan_to_html(&get_terms());
+
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains("<li>R&amp;D_1 (2)</li>"));
+        assert!(html.contains("<td class=\"number\">60%</td><td class=\"number\">10</td></tr>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_plan_to_latex() {
+        let latex: String = plan_to_latex(&get_terms());
+
+        assert!(latex.contains("1. Fall, year 1 & CS 101 (4) & 4 & 10 & 40\\% & 4 \\\\\n"));
+        assert!(latex.contains("R\\&D\\_1 (2)"));
+        assert!(latex.contains("\\caption{Course plan: 2 terms, 3 courses, 10 units total, 50\\% average utilisation.}"));
+        assert!(latex.ends_with("\\end{table}\n"));
+    }
+
+    #[test]
+    fn test_empty_report() {
+        assert!(plan_to_markdown(&[]).contains("0 terms, 0 courses, 0 units total, 0% average"));
+    }
+}
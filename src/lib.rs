@@ -1,11 +1,42 @@
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 
 use multimap::MultiMap;
 
-#[derive(Clone, Debug, PartialEq)]
+mod alternatives;
+pub mod catalog;
+mod credits;
+mod dependencies;
+mod diff;
+pub mod export;
+mod options;
+mod pins;
+mod plan;
+mod registration;
+mod replan;
+mod timing;
+mod trace;
+mod validation;
+
+pub use alternatives::{AlternativePlan, CourseMove, Objective, PlanScore};
+pub use credits::{CreditError, Credits};
+pub use diff::{diff_plans, PlanDiff, UnitChange};
+pub use options::ScheduleOptions;
+pub use pins::PinError;
+pub use plan::{Plan, SoftViolation};
+pub use registration::CourseBundle;
+pub use replan::Progress;
+pub use timing::CourseSlack;
+pub use trace::{CourseTrace, ScheduleTrace, SkipReason, TermSkip, TracedTerm};
+pub use validation::Violation;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TermType {
     Fall,
     Winter,
@@ -13,21 +44,183 @@ pub enum TermType {
     Summer,
 }
 
+impl fmt::Display for TermType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let term_name: &str = match self {
+            TermType::Fall => "Fall",
+            TermType::Winter => "Winter",
+            TermType::Spring => "Spring",
+            TermType::Summer => "Summer",
+        };
+
+        write!(f, "{}", term_name)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseTermTypeError(pub String);
+
+impl fmt::Display for ParseTermTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a term, expected Fall, Winter, Spring or Summer",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseTermTypeError {}
+
+/// A course that can not be taken together with another course because a
+/// sequence already sets its term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConcurrentInSequenceError(pub String);
+
+impl fmt::Display for ConcurrentInSequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is in a sequence, so it can not be taken together with another course",
+            self.0
+        )
+    }
+}
+
+impl Error for ConcurrentInSequenceError {}
+
+/// Why the scheduler could not plan the courses.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScheduleError {
+    /// Every course is already completed, or the catalog has none.
+    NoCourses,
+    /// The courses left, sorted by name, can never be scheduled, such as
+    /// when their prerequisites form a cycle or no term they are offered in
+    /// has room for them.
+    Unschedulable { courses: Vec<String> },
+    /// The units of a term or of a group of courses are too large to count.
+    CreditOverflow(CreditError),
+    /// The courses pinned by the options can not be planned where they are
+    /// pinned, as `Courses::check_pins` lists.
+    Pins(Vec<PinError>),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::NoCourses => write!(f, "there are no courses left to plan"),
+            ScheduleError::Unschedulable { courses } => {
+                write!(f, "{} can never be scheduled", courses.join(", "))
+            }
+            ScheduleError::CreditOverflow(e) => write!(f, "{}", e),
+            ScheduleError::Pins(errors) => {
+                let errors: Vec<String> = errors.iter().map(|x| x.to_string()).collect();
+                write!(f, "{}", errors.join("; "))
+            }
+        }
+    }
+}
+
+impl Error for ScheduleError {}
+
+impl From<CreditError> for ScheduleError {
+    fn from(e: CreditError) -> ScheduleError {
+        ScheduleError::CreditOverflow(e)
+    }
+}
+
+impl FromStr for TermType {
+    type Err = ParseTermTypeError;
+
+    fn from_str(text: &str) -> Result<TermType, ParseTermTypeError> {
+        match text.trim().to_lowercase().as_str() {
+            "fall" => Ok(TermType::Fall),
+            "winter" => Ok(TermType::Winter),
+            "spring" => Ok(TermType::Spring),
+            "summer" => Ok(TermType::Summer),
+            _ => Err(ParseTermTypeError(text.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Course {
     pub name: String,
-    pub credits: u8,
+    /// Credits for one instance of the course, or the fewest credits an
+    /// instance can be taken for when the course has variable credits.
+    pub credits: Credits,
+    pub max_credits: Credits,
+    /// How many instances of the course can be taken over the whole plan.
+    pub repeat_limit: u8,
+    /// Credits to earn from this course across all of its instances.
+    pub target_credits: Credits,
     pub availability: [bool; 4],
 }
 
 impl Course {
-    pub fn new(name: String, credits: u8) -> Course {
+    pub fn new(name: String, credits: impl Into<Credits>) -> Course {
+        let credits: Credits = credits.into();
+
         Course {
-            name: name,
-            credits: credits,
+            name,
+            credits,
+            max_credits: credits,
+            repeat_limit: 1,
+            target_credits: credits,
             availability: [false; 4],
         }
     }
 
+    /// Allows an instance of the course to be taken for anywhere from
+    /// `credits` up to `max_credits` units.
+    pub fn variable_credits(&mut self, max_credits: impl Into<Credits>) -> &mut Course {
+        self.max_credits = max_credits.into().max(self.credits);
+
+        self
+    }
+
+    pub fn repeatable(&mut self, repeat_limit: u8) -> &mut Course {
+        self.repeat_limit = repeat_limit.max(1);
+
+        self
+    }
+
+    /// Sets the credits to earn across every instance of the course, which
+    /// is never less than the credits of one instance.
+    pub fn set_target_credits(&mut self, target_credits: impl Into<Credits>) -> &mut Course {
+        self.target_credits = target_credits.into().max(self.credits);
+
+        self
+    }
+
+    pub fn is_variable(&self) -> bool {
+        self.max_credits > self.credits
+    }
+
+    pub fn is_repeatable(&self) -> bool {
+        self.repeat_limit > 1
+    }
+
+    /// Gets the units the course takes in a term when all of its target
+    /// credits are earned in that one term, as when it is taken along with
+    /// its sequence or concurrent courses.
+    pub fn get_single_term_units(&self) -> Credits {
+        let most_units: Credits = Credits::from_hundredths(
+            self.max_credits
+                .hundredths()
+                .saturating_mul(self.repeat_limit as u32),
+        );
+
+        self.target_credits.min(most_units)
+    }
+
+    /// Checks whether another instance of the course can be taken after the
+    /// given number of instances.
+    pub fn can_take_again(&self, instances_count: usize) -> bool {
+        instances_count < self.repeat_limit as usize
+    }
+
     pub fn available_by<'a>(&'a mut self, term: &TermType) -> &'a mut Course {
         let index: usize = term.clone() as usize;
 
@@ -57,24 +250,56 @@ impl Course {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Term {
     term_type: TermType,
-    courses: HashSet<(String, u8)>,
-    units: u8,
-    unit_limit: u8,
+    year: u16,
+    courses: Vec<(String, Credits)>,
+    units: Credits,
+    unit_limit: Credits,
 }
 
 impl Term {
-    pub fn new(term: &TermType, unit_limit: u8) -> Term {
+    pub fn new(term: &TermType, unit_limit: impl Into<Credits>) -> Term {
         Term {
             term_type: term.clone(),
-            courses: HashSet::new(),
-            units: 0,
-            unit_limit: unit_limit,
+            year: 1,
+            courses: Vec::new(),
+            units: Credits::ZERO,
+            unit_limit: unit_limit.into(),
         }
     }
 
+    /// Sets the year of the plan the term falls in, starting from 1. Each
+    /// year begins with a Fall term.
+    pub fn set_year(&mut self, year: u16) -> &mut Term {
+        self.year = year;
+        self
+    }
+
+    pub fn term_type(&self) -> &TermType {
+        &self.term_type
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn units(&self) -> Credits {
+        self.units
+    }
+
+    pub fn unit_limit(&self) -> Credits {
+        self.unit_limit
+    }
+
+    /// Gets each course instance in the term along with the units it is
+    /// taken for, in the order they were added.
+    pub fn courses(&self) -> &[(String, Credits)] {
+        &self.courses
+    }
+
     pub fn is_full(&self) -> bool {
         self.units == self.unit_limit
     }
@@ -83,33 +308,59 @@ impl Term {
         self.can_add_course_units(course.credits)
     }
 
-    pub fn can_add_course_units(&self, units: u8) -> bool {
-        units + self.units <= self.unit_limit
+    pub fn can_add_course_units(&self, units: Credits) -> bool {
+        match self.units.checked_add(units) {
+            Ok(x) => x <= self.unit_limit,
+            Err(_) => false,
+        }
     }
 
-    pub fn add(&mut self, course: &Course) {
-        let is_added = self.courses.insert((course.name.clone(), course.credits));
+    pub fn add(&mut self, course: &Course) -> Result<bool, CreditError> {
+        self.add_units(course, course.credits)
+    }
 
-        if is_added {
-            self.units += course.credits;
+    /// Adds one instance of the course taken for the given units, giving
+    /// false without adding it when the term already holds as many instances
+    /// as the course can be taken over the whole plan. Instances in other
+    /// terms count toward that limit too, which the scheduler and
+    /// `validate_terms` keep track of.
+    pub fn add_units(&mut self, course: &Course, units: Credits) -> Result<bool, CreditError> {
+        if !course.can_take_again(self.course_count(&course.name)) {
+            return Ok(false);
         }
+
+        self.units = self.units.checked_add(units)?;
+        self.courses.push((course.name.clone(), units));
+
+        Ok(true)
+    }
+
+    /// Removes the last instance of the course from the term, getting the
+    /// units it was taken for.
+    pub fn remove_course(&mut self, course_name: &str) -> Option<Credits> {
+        let index: usize = self.courses.iter().rposition(|x| x.0 == course_name)?;
+        let (_, units) = self.courses.remove(index);
+        self.units = self.units.saturating_sub(units);
+
+        Some(units)
     }
 
     pub fn is_empty(&self) -> bool {
-        self.courses.len() == 0
+        self.courses.is_empty()
+    }
+
+    pub fn has_course(&self, course_name: &str) -> bool {
+        self.courses.iter().any(|x| x.0 == course_name)
+    }
+
+    pub fn course_count(&self, course_name: &str) -> usize {
+        self.courses.iter().filter(|x| x.0 == course_name).count()
     }
 }
 
 impl fmt::Display for Term {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let term_name: &str = match self.term_type {
-            TermType::Fall => "Fall",
-            TermType::Winter => "Winter",
-            TermType::Spring => "Spring",
-            TermType::Summer => "Summer",
-        };
-
-        let term_header: String = format!("{}: {} units total\n", term_name, self.units);
+        let term_header: String = format!("{}: {} units total\n", self.term_type, self.units);
         let mut term_body: String = String::new();
 
         for course in &self.courses {
@@ -120,11 +371,18 @@ impl fmt::Display for Term {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Courses {
     master_list: HashMap<String, Course>,
     //VV TODO: Make copy of prereqs for processing VV
     prerequisites: MultiMap<String, String>,
+    prerequisite_choices: MultiMap<String, Vec<String>>,
     concurrencies: MultiMap<String, String>,
+    equivalencies: MultiMap<String, String>,
+    sequences: Vec<Vec<String>>,
+    recommendations: MultiMap<String, String>,
+    preferred_terms: HashMap<String, TermType>,
 }
 
 impl Courses {
@@ -132,7 +390,12 @@ impl Courses {
         Courses {
             master_list: HashMap::new(),
             prerequisites: MultiMap::new(),
+            prerequisite_choices: MultiMap::new(),
             concurrencies: MultiMap::new(),
+            equivalencies: MultiMap::new(),
+            sequences: Vec::new(),
+            recommendations: MultiMap::new(),
+            preferred_terms: HashMap::new(),
         }
     }
 
@@ -140,10 +403,52 @@ impl Courses {
         self.master_list.insert(course.name.clone(), course);
     }
 
+    /// Removes a course along with every relation that names it. Courses
+    /// that were taken together with it, or were equivalent through it, stay
+    /// related to each other.
     pub fn remove_course(&mut self, course_name: &String) -> Option<Course> {
+        self.sequences.retain(|x| !x.contains(course_name));
+
+        self.prerequisites
+            .retain(|k, v| k != course_name && v != course_name);
+        self.recommendations
+            .retain(|k, v| k != course_name && v != course_name);
+        self.preferred_terms.remove(course_name);
+
+        self.prerequisite_choices.remove(course_name);
+        for (.., choices) in self.prerequisite_choices.iter_all_mut() {
+            for choice in choices.iter_mut() {
+                choice.retain(|x| x != course_name);
+            }
+        }
+        self.prerequisite_choices.retain(|_k, v| !v.is_empty());
+
+        Courses::remove_from_group(&mut self.concurrencies, course_name);
+        Courses::remove_from_group(&mut self.equivalencies, course_name);
+
         self.master_list.remove(course_name)
     }
 
+    /// Removes a course from a relation kept in both directions, linking the
+    /// courses it was related to so that they stay in one group.
+    fn remove_from_group(relation: &mut MultiMap<String, String>, course_name: &String) {
+        let related_courses: Vec<String> = relation.remove(course_name).unwrap_or_default();
+
+        for course in &related_courses {
+            for other_course in &related_courses {
+                let is_linked = relation
+                    .get_vec(course)
+                    .is_some_and(|x| x.contains(other_course));
+
+                if course != other_course && !is_linked {
+                    relation.insert(course.clone(), other_course.clone());
+                }
+            }
+        }
+
+        relation.retain(|_k, v| v != course_name);
+    }
+
     fn add_prerequisite_to_concurrent(
         &mut self,
         course: &String,
@@ -193,23 +498,65 @@ impl Courses {
         None
     }
 
-    fn combine_concurrent_prerequisites(&mut self, course: &String, depends_on: &String) {
-        let course_prerequisites = match self.get_prerequisites(course) {
-            Some(x) => x,
-            None => HashSet::new(),
-        };
+    /// Requires taking at least one of the given courses before a course, as
+    /// in "CS 101 or CS 105". Like other prerequisites, the choice also
+    /// applies to the courses concurrent with it.
+    pub fn add_prerequisite_choice(&mut self, course: &String, options: &[String]) {
+        if options.len() == 1 {
+            self.add_prerequisite(course, &options[0]);
+            return;
+        } else if options.is_empty() {
+            return;
+        }
 
-        let dependent_prerequisites = match self.get_prerequisites(depends_on) {
-            Some(x) => x,
+        let mut choice_courses: HashSet<String> = match self.get_concurrents_for(course) {
+            Some(x) => x.0,
             None => HashSet::new(),
         };
+        choice_courses.insert(course.clone());
+
+        for choice_course in choice_courses {
+            self.prerequisite_choices
+                .insert(choice_course, options.to_vec());
+        }
+    }
+
+    pub fn get_prerequisite_choices(&self, course: &String) -> Option<Vec<Vec<String>>> {
+        self.prerequisite_choices.get_vec(course).cloned()
+    }
+
+    pub fn remove_prerequisite_choice(
+        &mut self,
+        course: &String,
+        options: &[String],
+    ) -> Option<Vec<String>> {
+        if let Some(c) = self.prerequisite_choices.get_vec_mut(course) {
+            if let Some(index) = c.iter().position(|x| x == options) {
+                return Some(c.remove(index));
+            }
+        }
+
+        None
+    }
+
+    fn get_pending_prerequisites(&self) -> PendingPrerequisites {
+        PendingPrerequisites {
+            required: self.prerequisites.clone(),
+            choices: self.prerequisite_choices.clone(),
+        }
+    }
+
+    fn combine_concurrent_prerequisites(&mut self, course: &String, depends_on: &String) {
+        let course_prerequisites = self.get_prerequisites(course).unwrap_or_default();
+
+        let dependent_prerequisites = self.get_prerequisites(depends_on).unwrap_or_default();
 
         let mut prereqs_to_add: HashSet<String> = course_prerequisites
             .difference(&dependent_prerequisites)
             .cloned()
             .collect();
         for prereq_course in &prereqs_to_add {
-            self.add_prerequisite(&depends_on, &prereq_course);
+            self.add_prerequisite(depends_on, prereq_course);
         }
 
         prereqs_to_add = dependent_prerequisites
@@ -217,12 +564,49 @@ impl Courses {
             .cloned()
             .collect();
         for prereq_course in &prereqs_to_add {
-            self.add_prerequisite(&course, &prereq_course);
+            self.add_prerequisite(course, prereq_course);
+        }
+
+        let course_choices = self.get_prerequisite_choices(course).unwrap_or_default();
+        let dependent_choices = self
+            .get_prerequisite_choices(depends_on)
+            .unwrap_or_default();
+
+        for choice in course_choices.iter() {
+            if !dependent_choices.contains(choice) {
+                self.prerequisite_choices
+                    .insert(depends_on.clone(), choice.clone());
+            }
+        }
+
+        for choice in dependent_choices.iter() {
+            if !course_choices.contains(choice) {
+                self.prerequisite_choices
+                    .insert(course.clone(), choice.clone());
+            }
         }
     }
 
-    pub fn add_concurrency(&mut self, course: &String, depends_on: &String) {
-        if self.prerequisites.contains_key(course) || self.prerequisites.contains_key(depends_on) {
+    /// Requires taking two courses in the same term. A course in a sequence
+    /// already has its term set by the sequence, so nothing is added when
+    /// either course is in one.
+    pub fn add_concurrency(
+        &mut self,
+        course: &String,
+        depends_on: &String,
+    ) -> Result<(), ConcurrentInSequenceError> {
+        if let Some(sequenced) = [course, depends_on]
+            .iter()
+            .find(|x| self.get_sequence_for(x).is_some())
+        {
+            return Err(ConcurrentInSequenceError(sequenced.to_string()));
+        }
+
+        if self.prerequisites.contains_key(course)
+            || self.prerequisites.contains_key(depends_on)
+            || self.prerequisite_choices.contains_key(course)
+            || self.prerequisite_choices.contains_key(depends_on)
+        {
             self.combine_concurrent_prerequisites(course, depends_on);
         }
 
@@ -230,16 +614,24 @@ impl Courses {
             .insert(course.clone(), depends_on.clone());
         self.concurrencies
             .insert(depends_on.clone(), course.clone());
+
+        Ok(())
     }
 
-    fn get_concurrents_with_memory(
-        &self,
+    fn is_concurrent(&self, course: &String) -> bool {
+        self.concurrencies
+            .get_vec(course)
+            .is_some_and(|x| !x.is_empty())
+    }
+
+    fn get_related_with_memory(
+        relation: &MultiMap<String, String>,
         course: &String,
         concurrents: &mut HashSet<String>,
     ) -> HashSet<String> {
         concurrents.insert(course.to_string());
 
-        if let Some(found_concurs) = self.concurrencies.get_vec(&course.to_string()) {
+        if let Some(found_concurs) = relation.get_vec(&course.to_string()) {
             let mut found_set: HashSet<String> = found_concurs.iter().cloned().collect();
 
             for concur in found_concurs.iter() {
@@ -248,7 +640,11 @@ impl Courses {
                 }
 
                 found_set = found_set
-                    .union(&self.get_concurrents_with_memory(concur, concurrents))
+                    .union(&Courses::get_related_with_memory(
+                        relation,
+                        concur,
+                        concurrents,
+                    ))
                     .cloned()
                     .collect();
             }
@@ -259,24 +655,31 @@ impl Courses {
         HashSet::new()
     }
 
-    fn get_concurrents_units(&self, concurrents: &HashSet<String>) -> u8 {
-        concurrents
+    /// Totals the units the concurrent courses take in their term.
+    fn get_concurrents_units(&self, concurrents: &HashSet<String>) -> Result<Credits, CreditError> {
+        // A concurrent course missing from the master list has nothing to
+        // take, so it adds no units.
+        let concurrent_credits = concurrents
             .iter()
-            .map(|x| {
-                self.master_list
-                    .get(x)
-                    .expect("Course in concurrents not found in master list.")
-            })
-            .map(|x| x.credits)
-            .sum()
+            .filter_map(|x| self.master_list.get(x))
+            .map(|x| x.get_single_term_units());
+
+        Credits::checked_sum(concurrent_credits)
     }
 
-    pub fn get_concurrents_for(&self, course: &String) -> Option<(HashSet<String>, u8)> {
+    /// Gets every course taken together with the course, including itself,
+    /// and the units they total, which is an error when the total is too
+    /// large to count.
+    pub fn get_concurrents_for(
+        &self,
+        course: &String,
+    ) -> Option<(HashSet<String>, Result<Credits, CreditError>)> {
         let mut seen_courses: HashSet<String> = HashSet::new();
 
-        let concurs_found = self.get_concurrents_with_memory(course, &mut seen_courses);
+        let concurs_found =
+            Courses::get_related_with_memory(&self.concurrencies, course, &mut seen_courses);
 
-        if concurs_found.len() > 0 {
+        if !concurs_found.is_empty() {
             let concurrent_units = self.get_concurrents_units(&concurs_found);
 
             return Some((concurs_found, concurrent_units));
@@ -314,112 +717,745 @@ impl Courses {
         Some((course.clone(), depends_on.clone()))
     }
 
-    pub fn get_term_courses_for(&self, term: &TermType) -> Vec<String> {
-        self.master_list
-            .iter()
-            .filter(|&x| x.1.is_available(term))
-            .map(|x| x.1.name.clone())
-            .collect()
+    /// Marks two courses as the same course, such as a cross-listed course or
+    /// an old course code and its renamed replacement. Equivalence carries
+    /// over to every course equivalent to either of them.
+    pub fn add_equivalence(&mut self, course: &str, equivalent_to: &str) {
+        self.equivalencies
+            .insert(course.to_string(), equivalent_to.to_string());
+        self.equivalencies
+            .insert(equivalent_to.to_string(), course.to_string());
     }
 
-    fn get_next_term_for(&self, term: TermType) -> TermType {
-        match term {
-            TermType::Fall => TermType::Winter,
-            TermType::Winter => TermType::Spring,
-            TermType::Spring => TermType::Summer,
-            TermType::Summer => TermType::Fall,
+    pub fn get_equivalents_for(&self, course: &String) -> Option<HashSet<String>> {
+        let mut seen_courses: HashSet<String> = HashSet::new();
+
+        let mut equivalents_found =
+            Courses::get_related_with_memory(&self.equivalencies, course, &mut seen_courses);
+        equivalents_found.remove(course);
+
+        if !equivalents_found.is_empty() {
+            return Some(equivalents_found);
         }
+
+        None
     }
 
-    pub fn get_terms(&self, term_unit_limits: [u8; 4]) -> Option<Vec<Term>> {
-        let mut completed_terms: Vec<Term> = Vec::new();
+    pub fn remove_equivalence(
+        &mut self,
+        course: &String,
+        equivalent_to: &String,
+    ) -> Option<(String, String)> {
+        let course_equivalents = self.equivalencies.get_vec_mut(course)?;
+        let equivalent_index = course_equivalents.iter().position(|x| x == equivalent_to)?;
 
-        let mut fall_courses: Vec<String> = self.get_term_courses_for(&TermType::Fall);
-        let mut winter_courses: Vec<String> = self.get_term_courses_for(&TermType::Winter);
-        let mut spring_courses: Vec<String> = self.get_term_courses_for(&TermType::Spring);
-        let mut summer_courses: Vec<String> = self.get_term_courses_for(&TermType::Summer);
-        let mut prerequisites: MultiMap<String, String> = self.prerequisites.clone();
+        course_equivalents.remove(equivalent_index);
 
-        let mut processed_term_courses: HashSet<String> = HashSet::new();
-        let total_courses_count = self.len();
+        if let Some(dependent_equivalents) = self.equivalencies.get_vec_mut(equivalent_to) {
+            dependent_equivalents.retain(|x| x != course);
+        }
 
-        let mut current_term = TermType::Fall;
+        Some((course.clone(), equivalent_to.clone()))
+    }
 
-        while processed_term_courses.len() < total_courses_count {
-            let current_term_index: usize = current_term.clone() as usize;
+    /// Records a course as scheduled along with every equivalent course in
+    /// the master list, so no equivalent of it gets scheduled as well.
+    fn mark_processed(&self, course: &String, processed_courses: &mut HashSet<String>) {
+        processed_courses.insert(course.clone());
+
+        if let Some(equivalents) = self.get_equivalents_for(course) {
+            processed_courses.extend(
+                equivalents
+                    .into_iter()
+                    .filter(|x| self.master_list.contains_key(x)),
+            );
+        }
+    }
 
-            let mut term: Term = Term::new(&current_term, term_unit_limits[current_term_index]);
-            let term_courses: &Vec<String> = match current_term {
-                TermType::Fall => &fall_courses,
-                TermType::Winter => &winter_courses,
-                TermType::Spring => &spring_courses,
-                TermType::Summer => &summer_courses,
-            };
+    /// Gets every course whose prerequisite requirement is met by finishing
+    /// the given term, which includes equivalents of the courses taken.
+    fn get_satisfied_courses(&self, term: &Term) -> HashSet<String> {
+        let mut satisfied_courses: HashSet<String> = HashSet::new();
 
-            for course_name in term_courses {
-                if term.is_full() {
-                    break;
-                }
+        for (course_name, ..) in &term.courses {
+            satisfied_courses.insert(course_name.clone());
 
-                let course: &Course = self.master_list.get(course_name).unwrap();
+            if let Some(equivalents) = self.get_equivalents_for(course_name) {
+                satisfied_courses.extend(equivalents);
+            }
+        }
 
-                if prerequisites.contains_key(course_name) || !term.can_add_course(course) {
-                    continue;
-                }
+        satisfied_courses
+    }
 
-                if let Some(course_concurrents) = self.get_concurrents_for(&course_name) {
-                    if !term.can_add_course_units(course_concurrents.1) {
-                        continue;
-                    }
+    /// Declares that the given courses must be taken one after another in
+    /// consecutive terms, in the order given (e.g. a Fall, Winter, Spring
+    /// capstone). A course can only belong to one sequence, so any existing
+    /// sequence sharing a course with this one is replaced. A course taken
+    /// together with another can not be in a sequence, so nothing is added
+    /// and false is given when any of the courses is concurrent.
+    pub fn add_sequence(&mut self, sequence: &[String]) -> bool {
+        if sequence.is_empty() || sequence.iter().any(|x| self.is_concurrent(x)) {
+            return false;
+        }
 
-                    for concur_course_name in course_concurrents.0 {
-                        let concur_course: &Course =
-                            self.master_list.get(&concur_course_name).unwrap();
+        self.sequences
+            .retain(|x| !x.iter().any(|course| sequence.contains(course)));
+        self.sequences.push(sequence.to_vec());
 
-                        term.add(&concur_course);
-                        processed_term_courses.insert(concur_course.name.clone());
-                    }
-                } else {
-                    term.add(&course);
-                    processed_term_courses.insert(course.name.clone());
-                }
-            }
+        true
+    }
 
-            fall_courses.retain(|x| !processed_term_courses.contains(x));
-            winter_courses.retain(|x| !processed_term_courses.contains(x));
-            spring_courses.retain(|x| !processed_term_courses.contains(x));
-            summer_courses.retain(|x| !processed_term_courses.contains(x));
+    pub fn get_sequence_for(&self, course: &String) -> Option<Vec<String>> {
+        self.sequences.iter().find(|x| x.contains(course)).cloned()
+    }
 
-            for (.., value) in prerequisites.iter_all_mut() {
-                value.retain(|x| !processed_term_courses.contains(x));
-            }
+    pub fn remove_sequence(&mut self, course: &String) -> Option<Vec<String>> {
+        let index = self.sequences.iter().position(|x| x.contains(course))?;
 
-            prerequisites.retain(|_k, v| v.len() > 0);
+        Some(self.sequences.remove(index))
+    }
 
-            if !term.is_empty() {
-                completed_terms.push(term);
+    /// Checks whether every course after the first in the sequence fits in
+    /// the terms following the current one, creating those terms if they
+    /// have not been planned yet.
+    fn can_schedule_sequence(
+        &self,
+        sequence: &[String],
+        current_term: &Term,
+        upcoming_terms: &mut VecDeque<Term>,
+        prerequisites: &PendingPrerequisites,
+        term_unit_limits: &[Credits; 4],
+        pinned_units: &HashMap<TracedTerm, Credits>,
+    ) -> bool {
+        while upcoming_terms.len() < sequence.len() - 1 {
+            let last_term: &Term = upcoming_terms.back().unwrap_or(current_term);
+            let next_term = self.get_next_term_for(last_term.term_type.clone());
+            let next_year = self.get_next_year_for(&next_term, last_term.year);
+
+            upcoming_terms.push_back(new_schedule_term(
+                &next_term,
+                next_year,
+                term_unit_limits,
+                pinned_units,
+            ));
+        }
+
+        for (index, course_name) in sequence.iter().enumerate().skip(1) {
+            let course: &Course = match self.master_list.get(course_name) {
+                Some(x) => x,
+                None => return false,
+            };
+
+            let term: &Term = &upcoming_terms[index - 1];
+            if !course.is_available(&term.term_type)
+                || !term.can_add_course_units(course.get_single_term_units())
+            {
+                return false;
             }
 
-            current_term = self.get_next_term_for(current_term);
+            if prerequisites.blocks_after(course_name, &sequence[..index]) {
+                return false;
+            }
         }
 
-        if completed_terms.len() > 0 {
-            return Some(completed_terms);
+        true
+    }
+
+    /// Recommends taking a course before another without requiring it. The
+    /// scheduler follows recommendations when it can, and a plan lists the
+    /// ones it could not follow.
+    pub fn add_recommendation(&mut self, course: &str, recommended: &str) {
+        self.recommendations
+            .insert(course.to_string(), recommended.to_string());
+    }
+
+    pub fn get_recommendations_for(&self, course: &String) -> Option<HashSet<String>> {
+        if let Some(found_recommendations) = self.recommendations.get_vec(course) {
+            return Some(found_recommendations.iter().cloned().collect());
         }
 
         None
     }
 
-    pub fn len(&self) -> usize {
-        self.master_list.len()
+    pub fn remove_recommendation(
+        &mut self,
+        course: &String,
+        recommended: &String,
+    ) -> Option<String> {
+        if let Some(c) = self.recommendations.get_vec_mut(course) {
+            if let Some(index) = c.iter().position(|x| x == recommended) {
+                return Some(c.remove(index));
+            }
+        }
+
+        None
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Prefers taking a course in the given term, which the scheduler follows
+    /// when it can, like a recommendation.
+    pub fn prefer_term(&mut self, course: &str, term: &TermType) {
+        self.preferred_terms
+            .insert(course.to_string(), term.clone());
+    }
 
-    #[test]
+    pub fn get_preferred_term_for(&self, course: &String) -> Option<TermType> {
+        self.preferred_terms.get(course).cloned()
+    }
+
+    pub fn remove_preferred_term(&mut self, course: &String) -> Option<TermType> {
+        self.preferred_terms.remove(course)
+    }
+
+    /// Checks whether taking the course in the given term follows its
+    /// recommendations and preferred term. Preferences that waiting can never
+    /// meet are ignored: a preferred term the course is not offered in or
+    /// has no room for, and a recommended course that is not in the catalog
+    /// or can only be taken after the course.
+    fn is_soft_satisfied(
+        &self,
+        course: &String,
+        term: &TermType,
+        completed_courses: &HashSet<String>,
+        term_unit_limits: &[Credits; 4],
+    ) -> bool {
+        if let Some(preferred_term) = self.preferred_terms.get(course) {
+            let can_take_preferred = self.master_list.get(course).is_some_and(|x| {
+                x.is_available(preferred_term)
+                    && x.credits <= term_unit_limits[preferred_term.clone() as usize]
+            });
+
+            if can_take_preferred && preferred_term != term {
+                return false;
+            }
+        }
+
+        match self.recommendations.get_vec(course) {
+            Some(recommended) => recommended.iter().all(|x| {
+                let is_in_catalog = self.master_list.contains_key(x)
+                    || self
+                        .get_equivalents_for(x)
+                        .is_some_and(|y| y.iter().any(|z| self.master_list.contains_key(z)));
+
+                completed_courses.contains(x)
+                    || !is_in_catalog
+                    || self.get_all_prerequisites(x).contains(course)
+            }),
+            None => true,
+        }
+    }
+
+    pub fn get_term_courses_for(&self, term: &TermType) -> Vec<String> {
+        self.master_list
+            .iter()
+            .filter(|&x| x.1.is_available(term))
+            .map(|x| x.1.name.clone())
+            .collect()
+    }
+
+    fn get_next_term_for(&self, term: TermType) -> TermType {
+        match term {
+            TermType::Fall => TermType::Winter,
+            TermType::Winter => TermType::Spring,
+            TermType::Spring => TermType::Summer,
+            TermType::Summer => TermType::Fall,
+        }
+    }
+
+    fn get_next_year_for(&self, next_term: &TermType, year: u16) -> u16 {
+        match next_term {
+            TermType::Fall => year.saturating_add(1),
+            _ => year,
+        }
+    }
+
+    /// Adds as many instances of the course to the term as it takes to reach
+    /// the course's target credits, choosing the units for each instance to
+    /// fit what is left in the term. `earned` tracks the credits and
+    /// instances taken so far, and true is returned once the target is met.
+    fn add_course_instances(
+        &self,
+        term: &mut Term,
+        course: &Course,
+        earned: &mut (Credits, u8),
+    ) -> Result<bool, CreditError> {
+        while earned.0 < course.target_credits && course.can_take_again(earned.1 as usize) {
+            let remaining_credits = course.target_credits.saturating_sub(earned.0);
+            let term_room = term.unit_limit.saturating_sub(term.units);
+
+            let units = remaining_credits
+                .clamp(course.credits, course.max_credits)
+                .min(term_room);
+            if units < course.credits {
+                break;
+            }
+
+            if !term.add_units(course, units)? {
+                break;
+            }
+            earned.0 = earned.0.checked_add(units)?;
+            earned.1 += 1;
+        }
+
+        Ok(earned.0 >= course.target_credits)
+    }
+
+    /// Schedules every course, giving None when they can not be scheduled.
+    /// `get_terms_with` tells why.
+    pub fn get_terms(&self, term_unit_limits: [impl Into<Credits>; 4]) -> Option<Vec<Term>> {
+        self.get_terms_with(&ScheduleOptions::new(term_unit_limits))
+            .ok()
+    }
+
+    /// Schedules the courses that are not completed yet, starting from the
+    /// term the options give and around the courses they pin.
+    pub fn get_terms_with(&self, options: &ScheduleOptions) -> Result<Vec<Term>, ScheduleError> {
+        self.schedule(options).map(|x| x.0)
+    }
+
+    /// Schedules every course like `get_terms`, also reporting how well the
+    /// schedule follows the soft preferences of the courses.
+    pub fn get_plan(&self, term_unit_limits: [impl Into<Credits>; 4]) -> Option<Plan> {
+        self.get_plan_with(&ScheduleOptions::new(term_unit_limits))
+            .ok()
+    }
+
+    /// Schedules the courses like `get_terms_with`, also recording the
+    /// decisions made about each course when the options ask for a trace.
+    pub fn get_plan_with(&self, options: &ScheduleOptions) -> Result<Plan, ScheduleError> {
+        let (terms, trace) = self.schedule(options)?;
+
+        Ok(Plan::new(self, terms).with_trace(trace))
+    }
+
+    /// Checks terms planned elsewhere, such as by a student, against the
+    /// rules `get_terms` follows, listing every rule they break.
+    pub fn validate_terms(&self, terms: &[Term]) -> Vec<Violation> {
+        validation::validate(self, terms, &ScheduleOptions::new([Credits::ZERO; 4]))
+    }
+
+    /// Checks the terms like `validate_terms`, counting the completed courses
+    /// of the options as taken before the first term. Each term is held to
+    /// its own unit limit rather than the limits of the options.
+    pub fn validate_terms_with(&self, terms: &[Term], options: &ScheduleOptions) -> Vec<Violation> {
+        validation::validate(self, terms, options)
+    }
+
+    fn schedule(
+        &self,
+        options: &ScheduleOptions,
+    ) -> Result<(Vec<Term>, Option<ScheduleTrace>), ScheduleError> {
+        let term_unit_limits: [Credits; 4] = options.unit_limits;
+        let mut completed_terms: Vec<Term> = Vec::new();
+
+        let mut fall_courses: Vec<String> = self.get_term_courses_for(&TermType::Fall);
+        let mut winter_courses: Vec<String> = self.get_term_courses_for(&TermType::Winter);
+        let mut spring_courses: Vec<String> = self.get_term_courses_for(&TermType::Spring);
+        let mut summer_courses: Vec<String> = self.get_term_courses_for(&TermType::Summer);
+        let mut prerequisites: PendingPrerequisites = self.get_pending_prerequisites();
+
+        if !options.course_order.is_empty() {
+            let positions: HashMap<&String, usize> = options
+                .course_order
+                .iter()
+                .enumerate()
+                .map(|(index, x)| (x, index))
+                .collect();
+
+            for term_courses in [
+                &mut fall_courses,
+                &mut winter_courses,
+                &mut spring_courses,
+                &mut summer_courses,
+            ] {
+                term_courses.sort_by_key(|x| positions.get(x).copied().unwrap_or(usize::MAX));
+            }
+        }
+
+        let mut processed_term_courses: HashSet<String> = HashSet::new();
+        let mut completed_courses: HashSet<String> = HashSet::new();
+        let total_courses_count = self.len();
+
+        let mut earned_credits: HashMap<String, (Credits, u8)> = HashMap::new();
+
+        for course_name in &options.completed_courses {
+            if self.master_list.contains_key(course_name) {
+                self.mark_processed(course_name, &mut processed_term_courses);
+            }
+
+            completed_courses.insert(course_name.clone());
+            if let Some(equivalents) = self.get_equivalents_for(course_name) {
+                completed_courses.extend(equivalents);
+            }
+        }
+        prerequisites.complete(&completed_courses);
+
+        let mut trace: Option<ScheduleTrace> = match options.is_traced {
+            true => Some(ScheduleTrace::new(&completed_courses)),
+            false => None,
+        };
+        let mut skip_reasons: HashMap<String, SkipReason> = HashMap::new();
+
+        let mut pinned_terms: HashMap<String, TracedTerm> = HashMap::new();
+        let mut pinned_units: HashMap<TracedTerm, Credits> = HashMap::new();
+        let mut pinned_courses: HashSet<String> = HashSet::new();
+        if !options.pinned_courses.is_empty() {
+            let pin_errors: Vec<PinError> = self.check_pins(options);
+            if !pin_errors.is_empty() {
+                return Err(ScheduleError::Pins(pin_errors));
+            }
+            pinned_terms = self.get_pinned_terms(options, &mut Vec::new());
+            pinned_units = self.get_pinned_units(&pinned_terms)?;
+
+            // Equivalents of a pinned course would stand in for it if they
+            // were taken first, and the prerequisites of pinned courses are
+            // taken as early as possible to be done in time.
+            let mut pinned_prerequisites: HashSet<String> = HashSet::new();
+            for course_name in pinned_terms.keys() {
+                pinned_courses.insert(course_name.clone());
+                pinned_courses.extend(self.get_equivalents_for(course_name).unwrap_or_default());
+                pinned_prerequisites.extend(self.get_all_prerequisites(course_name));
+            }
+
+            for term_courses in [
+                &mut fall_courses,
+                &mut winter_courses,
+                &mut spring_courses,
+                &mut summer_courses,
+            ] {
+                term_courses.sort_by_key(|x| !pinned_prerequisites.contains(x));
+            }
+        }
+
+        let mut upcoming_terms: VecDeque<Term> = VecDeque::new();
+        let mut idle_terms_count = 0;
+        let mut held_terms_counts: HashMap<String, usize> = HashMap::new();
+
+        let mut current_term = options.start_term.clone();
+        let mut current_year: u16 = 1;
+
+        while processed_term_courses.len() < total_courses_count || !upcoming_terms.is_empty() {
+            let current_term_index: usize = current_term.clone() as usize;
+
+            let mut term: Term = match upcoming_terms.pop_front() {
+                Some(x) => x,
+                None => new_schedule_term(
+                    &current_term,
+                    current_year,
+                    &term_unit_limits,
+                    &pinned_units,
+                ),
+            };
+            // The units kept free for the pinned courses are given back now
+            // that they are placed first.
+            term.unit_limit = term_unit_limits[current_term_index];
+            let term_courses: &Vec<String> = match current_term {
+                TermType::Fall => &fall_courses,
+                TermType::Winter => &winter_courses,
+                TermType::Spring => &spring_courses,
+                TermType::Summer => &summer_courses,
+            };
+            let processed_before: Option<HashSet<String>> =
+                trace.as_ref().map(|_| processed_term_courses.clone());
+            skip_reasons.clear();
+
+            let mut term_pins: Vec<&String> = pinned_terms
+                .iter()
+                .filter(|x| x.1.term == current_term && x.1.year == current_year)
+                .map(|x| x.0)
+                .collect();
+            term_pins.sort();
+
+            for course_name in term_pins {
+                let course: &Course = self.master_list.get(course_name).unwrap();
+
+                if prerequisites.blocks(course_name) {
+                    return Err(self.get_unschedulable_error(&processed_term_courses));
+                }
+
+                let earned = earned_credits.entry(course_name.clone()).or_default();
+                if !self.add_course_instances(&mut term, course, earned)? {
+                    return Err(self.get_unschedulable_error(&processed_term_courses));
+                }
+                self.mark_processed(course_name, &mut processed_term_courses);
+            }
+
+            for course_name in term_courses {
+                if term.is_full() {
+                    break;
+                }
+
+                let course: &Course = self.master_list.get(course_name).unwrap();
+
+                if processed_term_courses.contains(course_name) || prerequisites.blocks(course_name)
+                {
+                    continue;
+                }
+
+                if pinned_courses.contains(course_name) {
+                    skip_reasons.insert(course_name.clone(), SkipReason::Pinned);
+                    continue;
+                }
+
+                // A course waits for its soft preferences for at most a full
+                // cycle of terms, as waiting longer can not help them.
+                if !self.is_soft_satisfied(
+                    course_name,
+                    &current_term,
+                    &completed_courses,
+                    &term_unit_limits,
+                ) {
+                    let held_terms_count =
+                        held_terms_counts.entry(course_name.clone()).or_default();
+
+                    if *held_terms_count < 4 {
+                        *held_terms_count += 1;
+                        skip_reasons.insert(course_name.clone(), SkipReason::SoftPreference);
+                        continue;
+                    }
+                }
+
+                if !term.can_add_course(course) {
+                    skip_reasons.insert(course_name.clone(), SkipReason::UnitLimit);
+                    continue;
+                }
+
+                if let Some(sequence) = self.get_sequence_for(course_name) {
+                    if sequence[0] != *course_name
+                        || !term.can_add_course_units(course.get_single_term_units())
+                        || !self.can_schedule_sequence(
+                            &sequence,
+                            &term,
+                            &mut upcoming_terms,
+                            &prerequisites,
+                            &term_unit_limits,
+                            &pinned_units,
+                        )
+                    {
+                        continue;
+                    }
+
+                    // Each course of a sequence or concurrency group has to
+                    // earn its target credits in the term it is placed in.
+                    let earned = earned_credits.entry(course.name.clone()).or_default();
+                    self.add_course_instances(&mut term, course, earned)?;
+                    self.mark_processed(&course.name, &mut processed_term_courses);
+
+                    for (sequence_course_name, upcoming_term) in
+                        sequence.iter().skip(1).zip(upcoming_terms.iter_mut())
+                    {
+                        let sequence_course: &Course =
+                            self.master_list.get(sequence_course_name).unwrap();
+
+                        let earned = earned_credits
+                            .entry(sequence_course.name.clone())
+                            .or_default();
+                        self.add_course_instances(upcoming_term, sequence_course, earned)?;
+                        self.mark_processed(&sequence_course.name, &mut processed_term_courses);
+                    }
+                } else if let Some((concurrents, units)) = self.get_concurrents_for(course_name) {
+                    let units: Credits = units?;
+                    if !term.can_add_course_units(units) {
+                        skip_reasons.insert(
+                            course_name.clone(),
+                            SkipReason::ConcurrentsTooLarge { units },
+                        );
+                        continue;
+                    }
+
+                    for concur_course_name in concurrents {
+                        if processed_term_courses.contains(&concur_course_name) {
+                            continue;
+                        }
+
+                        let concur_course: &Course =
+                            self.master_list.get(&concur_course_name).unwrap();
+
+                        let earned = earned_credits
+                            .entry(concur_course.name.clone())
+                            .or_default();
+                        self.add_course_instances(&mut term, concur_course, earned)?;
+                        self.mark_processed(&concur_course.name, &mut processed_term_courses);
+                    }
+                } else {
+                    let earned = earned_credits
+                        .entry(course.name.clone())
+                        .or_insert((Credits::ZERO, 0));
+
+                    if self.add_course_instances(&mut term, course, earned)? {
+                        self.mark_processed(&course.name, &mut processed_term_courses);
+                    }
+                }
+            }
+
+            fall_courses.retain(|x| !processed_term_courses.contains(x));
+            winter_courses.retain(|x| !processed_term_courses.contains(x));
+            spring_courses.retain(|x| !processed_term_courses.contains(x));
+            summer_courses.retain(|x| !processed_term_courses.contains(x));
+
+            let satisfied_courses: HashSet<String> = self.get_satisfied_courses(&term);
+
+            if let (Some(trace), Some(processed_before)) = (&mut trace, &processed_before) {
+                trace.record_term(
+                    self,
+                    &term,
+                    &prerequisites,
+                    processed_before,
+                    &processed_term_courses,
+                    &skip_reasons,
+                );
+                trace.complete_term(self, &term, &satisfied_courses);
+            }
+
+            prerequisites.complete(&satisfied_courses);
+            completed_courses.extend(satisfied_courses);
+
+            let has_pending_pins: bool = pinned_terms
+                .keys()
+                .any(|x| !processed_term_courses.contains(x));
+            let has_held_courses: bool = skip_reasons
+                .values()
+                .any(|x| *x == SkipReason::SoftPreference);
+
+            if term.is_empty() && (has_pending_pins || has_held_courses) {
+                // A term may stay empty while waiting for a pinned course or
+                // for the soft preferences of a course.
+            } else if term.is_empty() {
+                // Nothing changes between years once a full cycle of terms
+                // goes by without progress, so the remaining courses can
+                // never be scheduled.
+                idle_terms_count += 1;
+                if idle_terms_count == 4 {
+                    return Err(self.get_unschedulable_error(&processed_term_courses));
+                }
+            } else {
+                idle_terms_count = 0;
+                completed_terms.push(term);
+            }
+
+            current_term = self.get_next_term_for(current_term);
+            current_year = self.get_next_year_for(&current_term, current_year);
+        }
+
+        if !completed_terms.is_empty() {
+            return Ok((completed_terms, trace));
+        }
+
+        Err(ScheduleError::NoCourses)
+    }
+
+    fn get_unschedulable_error(&self, processed_courses: &HashSet<String>) -> ScheduleError {
+        let mut courses: Vec<String> = self
+            .master_list
+            .keys()
+            .filter(|x| !processed_courses.contains(*x))
+            .cloned()
+            .collect();
+        courses.sort();
+
+        ScheduleError::Unschedulable { courses }
+    }
+
+    pub fn get_course(&self, course: &str) -> Option<&Course> {
+        self.master_list.get(course)
+    }
+
+    /// Gets the names of every course in the catalog in alphabetical order.
+    pub fn get_course_names(&self) -> Vec<String> {
+        let mut course_names: Vec<String> = self.master_list.keys().cloned().collect();
+        course_names.sort();
+        course_names
+    }
+
+    pub fn len(&self) -> usize {
+        self.master_list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.master_list.is_empty()
+    }
+}
+
+/// Creates a term for the scheduler, keeping the units of the courses pinned
+/// to it free so that courses placed ahead of time, like the rest of a
+/// sequence, can not take their room.
+fn new_schedule_term(
+    term_type: &TermType,
+    year: u16,
+    term_unit_limits: &[Credits; 4],
+    pinned_units: &HashMap<TracedTerm, Credits>,
+) -> Term {
+    let traced_term = TracedTerm {
+        term: term_type.clone(),
+        year,
+    };
+    let reserved_units: Credits = pinned_units
+        .get(&traced_term)
+        .copied()
+        .unwrap_or(Credits::ZERO);
+
+    let mut term = Term::new(
+        term_type,
+        term_unit_limits[term_type.clone() as usize].saturating_sub(reserved_units),
+    );
+    term.set_year(year);
+
+    term
+}
+
+/// The prerequisites still standing between each course and being scheduled,
+/// which shrink as the scheduler completes courses.
+struct PendingPrerequisites {
+    required: MultiMap<String, String>,
+    choices: MultiMap<String, Vec<String>>,
+}
+
+impl PendingPrerequisites {
+    fn blocks(&self, course: &String) -> bool {
+        self.required.contains_key(course) || self.choices.contains_key(course)
+    }
+
+    /// Checks whether the course would still be blocked after also taking
+    /// the given courses.
+    fn blocks_after(&self, course: &String, taken: &[String]) -> bool {
+        let is_required_blocked = self
+            .required
+            .get_vec(course)
+            .is_some_and(|x| x.iter().any(|y| !taken.contains(y)));
+
+        let is_choice_blocked = self
+            .choices
+            .get_vec(course)
+            .is_some_and(|x| x.iter().any(|y| !y.iter().any(|z| taken.contains(z))));
+
+        is_required_blocked || is_choice_blocked
+    }
+
+    fn complete(&mut self, satisfied_courses: &HashSet<String>) {
+        for (.., value) in self.required.iter_all_mut() {
+            value.retain(|x| !satisfied_courses.contains(x));
+        }
+
+        for (.., value) in self.choices.iter_all_mut() {
+            value.retain(|x| !x.iter().any(|y| satisfied_courses.contains(y)));
+        }
+
+        self.required.retain(|_k, v| !v.is_empty());
+        self.choices.retain(|_k, v| !v.is_empty());
+    }
+}
+
+impl Default for Courses {
+    fn default() -> Courses {
+        Courses::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
     fn test_course_all_available() {
         let course_name: String = String::from("Test");
         let term = TermType::Fall;
@@ -468,6 +1504,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remove_course_relations() {
+        let mut courses: Courses = Courses::new();
+        for course_name in ["CS 101", "CS 105", "CS 201", "CS 201L", "CS 201P", "CS 301"] {
+            courses.add_course(Course::new(String::from(course_name), 4));
+        }
+
+        let course_name = String::from("CS 201");
+        let lab_name = String::from("CS 201L");
+        let project_name = String::from("CS 201P");
+        courses.add_concurrency(&course_name, &lab_name).unwrap();
+        courses
+            .add_concurrency(&course_name, &project_name)
+            .unwrap();
+        courses.add_prerequisite_choice(
+            &course_name,
+            &[String::from("CS 101"), String::from("CS 105")],
+        );
+        courses.add_prerequisite(&String::from("CS 301"), &course_name);
+        courses.add_prerequisite_choice(
+            &String::from("CS 301"),
+            &[String::from("CS 105"), course_name.clone()],
+        );
+        courses.add_equivalence("CS 101", "CS 201");
+        courses.add_equivalence("CS 201", "CS 105");
+        courses.add_recommendation("CS 301", "CS 201");
+        courses.prefer_term("CS 201", &TermType::Fall);
+
+        assert!(courses.remove_course(&course_name).is_some());
+        assert!(courses.get_course(&course_name).is_none());
+
+        let concurrents: HashSet<String> =
+            vec![lab_name.clone(), project_name].into_iter().collect();
+        assert_eq!(
+            courses.get_concurrents_for(&lab_name).map(|x| x.0),
+            Some(concurrents)
+        );
+        assert_eq!(courses.get_prerequisites(&String::from("CS 301")), None);
+        assert_eq!(
+            courses.get_prerequisite_choices(&String::from("CS 301")),
+            Some(vec![vec![String::from("CS 105")]])
+        );
+        assert_eq!(
+            courses.get_equivalents_for(&String::from("CS 101")),
+            Some(vec![String::from("CS 105")].into_iter().collect())
+        );
+        assert_eq!(
+            courses.get_recommendations_for(&String::from("CS 301")),
+            None
+        );
+        assert_eq!(courses.get_preferred_term_for(&course_name), None);
+
+        assert!(courses.get_terms([8, 8, 8, 8]).is_some());
+    }
+
     #[test]
     fn test_get_concurrents_none() {
         let courses: Courses = Courses::new();
@@ -491,8 +1582,12 @@ mod tests {
         courses.add_course(second_course);
         courses.add_course(third_course);
 
-        courses.add_concurrency(&first_course_name, &second_course_name);
-        courses.add_concurrency(&second_course_name, &third_course_name);
+        courses
+            .add_concurrency(&first_course_name, &second_course_name)
+            .unwrap();
+        courses
+            .add_concurrency(&second_course_name, &third_course_name)
+            .unwrap();
 
         let test1_concurrents = courses.get_concurrents_for(&first_course_name);
         assert_ne!(test1_concurrents, None);
@@ -505,7 +1600,7 @@ mod tests {
             courses.get_concurrents_for(&third_course_name)
         );
 
-        assert_eq!(test1_concurrents.unwrap().1, 12);
+        assert_eq!(test1_concurrents.unwrap().1, Ok(Credits::from(12)));
     }
 
     #[test]
@@ -561,60 +1656,657 @@ mod tests {
     }
 
     #[test]
-    fn test_concurrents_with_new_prerequisite() {
+    fn test_get_term_with_sequence() {
         let mut courses: Courses = Courses::new();
-        let first_course: Course = Course::new(String::from("Test1"), 3);
+        let first_course: Course = Course::new(String::from("CS 490A"), 4);
         let first_course_name = first_course.name.to_string();
 
-        let second_course: Course = Course::new(String::from("Test2"), 4);
+        let second_course: Course = Course::new(String::from("CS 490B"), 4);
         let second_course_name = second_course.name.to_string();
 
-        let third_course: Course = Course::new(String::from("Test3"), 5);
+        let third_course: Course = Course::new(String::from("CS 490C"), 4);
         let third_course_name = third_course.name.to_string();
 
-        let fourth_course: Course = Course::new(String::from("Test4"), 4);
-        let fourth_course_name = fourth_course.name.to_string();
+        let fourth_course: Course = Course::new(String::from("CS 10"), 4);
 
         courses.add_course(first_course);
         courses.add_course(second_course);
         courses.add_course(third_course);
         courses.add_course(fourth_course);
 
-        courses.add_concurrency(&first_course_name, &second_course_name);
-        courses.add_concurrency(&first_course_name, &third_course_name);
+        courses.add_sequence(&[
+            first_course_name.clone(),
+            second_course_name.clone(),
+            third_course_name.clone(),
+        ]);
+        assert_eq!(
+            courses.get_sequence_for(&second_course_name),
+            Some(vec![
+                first_course_name.clone(),
+                second_course_name.clone(),
+                third_course_name.clone()
+            ])
+        );
 
-        let test1_concurrents_results = courses.get_concurrents_for(&first_course_name);
-        assert_ne!(test1_concurrents_results, None);
+        let result: Option<Vec<Term>> = courses.get_terms([8, 8, 8, 8]);
+        assert_ne!(result, None);
 
-        let test1_concurrents: (HashSet<String>, u8) = test1_concurrents_results.unwrap();
-        assert_eq!(test1_concurrents.0.len(), 3);
-        assert_eq!(courses.get_prerequisites(&first_course_name), None);
+        let completed_terms: Vec<Term> = result.unwrap();
 
-        courses.add_prerequisite(&first_course_name, &fourth_course_name);
-        assert_ne!(courses.get_prerequisites(&first_course_name), None);
-        assert_ne!(courses.get_prerequisites(&second_course_name), None);
-        assert_ne!(courses.get_prerequisites(&third_course_name), None);
+        for term in &completed_terms {
+            println!("{}", term);
+        }
+
+        let first_term_index = completed_terms
+            .iter()
+            .position(|x| x.has_course(&first_course_name))
+            .unwrap();
+        assert!(completed_terms[first_term_index + 1].has_course(&second_course_name));
+        assert!(completed_terms[first_term_index + 2].has_course(&third_course_name));
     }
 
     #[test]
-    fn test_concurrents_with_existing_prerequisites() {
+    fn test_get_term_with_sequence_waits_for_room() {
         let mut courses: Courses = Courses::new();
-        let first_course: Course = Course::new(String::from("Test1"), 3);
+        let first_course: Course = Course::new(String::from("CS 490A"), 4);
         let first_course_name = first_course.name.to_string();
 
-        let second_course: Course = Course::new(String::from("Test2"), 4);
+        let second_course: Course = Course::new(String::from("CS 490B"), 8);
         let second_course_name = second_course.name.to_string();
 
-        let third_course: Course = Course::new(String::from("Test3"), 5);
-        let third_course_name = third_course.name.to_string();
+        courses.add_course(first_course);
+        courses.add_course(second_course);
 
-        let fourth_course: Course = Course::new(String::from("Test4"), 4);
-        let fourth_course_name = fourth_course.name.to_string();
+        courses.add_sequence(&[first_course_name.clone(), second_course_name.clone()]);
 
-        let fifth_course: Course = Course::new(String::from("Test5"), 3);
-        let fifth_course_name = fifth_course.name.to_string();
+        let result: Option<Vec<Term>> = courses.get_terms([8, 4, 4, 4]);
+        assert_ne!(result, None);
 
-        let sixth_course: Course = Course::new(String::from("Test6"), 4);
+        let completed_terms: Vec<Term> = result.unwrap();
+        assert_eq!(completed_terms.len(), 2);
+        assert_eq!(completed_terms[0].term_type, TermType::Summer);
+        assert!(completed_terms[0].has_course(&first_course_name));
+        assert_eq!(completed_terms[1].term_type, TermType::Fall);
+        assert!(completed_terms[1].has_course(&second_course_name));
+    }
+
+    #[test]
+    fn test_get_term_with_variable_credits() {
+        let mut courses: Courses = Courses::new();
+        let mut first_course: Course = Course::new(String::from("CS 199"), 1);
+        first_course
+            .variable_credits(4)
+            .repeatable(3)
+            .set_target_credits(6);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 10"), 4);
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+
+        let result: Option<Vec<Term>> = courses.get_terms([6, 6, 6, 6]);
+        assert_ne!(result, None);
+
+        let completed_terms: Vec<Term> = result.unwrap();
+
+        for term in &completed_terms {
+            println!("{}", term);
+        }
+
+        let research_units: Result<Credits, CreditError> = Credits::checked_sum(
+            completed_terms
+                .iter()
+                .flat_map(|x| x.courses.iter())
+                .filter(|x| x.0 == first_course_name)
+                .map(|x| x.1),
+        );
+        assert_eq!(research_units, Ok(Credits::from(6)));
+        assert!(completed_terms.iter().all(|x| x.units <= Credits::from(6)));
+    }
+
+    #[test]
+    fn test_get_term_with_variable_credits_in_groups() {
+        let mut courses: Courses = Courses::new();
+        let mut first_course: Course = Course::new(String::from("CS 490A"), 2);
+        first_course.variable_credits(4).set_target_credits(4);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 490B"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        let third_course: Course = Course::new(String::from("CS 201"), 4);
+        let third_course_name = third_course.name.to_string();
+
+        let mut fourth_course: Course = Course::new(String::from("CS 201L"), 1);
+        fourth_course.repeatable(2).set_target_credits(2);
+        let fourth_course_name = fourth_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_course(third_course);
+        courses.add_course(fourth_course);
+
+        courses.add_sequence(&[first_course_name.clone(), second_course_name]);
+        courses
+            .add_concurrency(&third_course_name, &fourth_course_name)
+            .unwrap();
+        assert_eq!(
+            courses.get_concurrents_for(&third_course_name).unwrap().1,
+            Ok(Credits::from(6))
+        );
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+        assert!(courses.validate_terms(&completed_terms).is_empty());
+
+        let lab_term: &Term = completed_terms
+            .iter()
+            .find(|x| x.has_course(&third_course_name))
+            .unwrap();
+        assert_eq!(lab_term.course_count(&fourth_course_name), 2);
+
+        let capstone_units: Vec<Credits> = completed_terms
+            .iter()
+            .flat_map(|x| x.courses.iter())
+            .filter(|x| x.0 == first_course_name)
+            .map(|x| x.1)
+            .collect();
+        assert_eq!(capstone_units, vec![Credits::from(4)]);
+    }
+
+    #[test]
+    fn test_course_target_credits_at_least_credits() {
+        let mut course: Course = Course::new(String::from("CS 199"), 2);
+        course.set_target_credits(0);
+
+        assert_eq!(course.target_credits, Credits::from(2));
+    }
+
+    #[test]
+    fn test_term_repeated_course_instances() {
+        let mut course: Course = Course::new(String::from("CS 199"), 2);
+        course.repeatable(2);
+
+        let mut term: Term = Term::new(&TermType::Fall, 8);
+        assert_eq!(term.add(&course), Ok(true));
+        assert_eq!(term.add(&course), Ok(true));
+        assert_eq!(term.add(&course), Ok(false));
+
+        assert_eq!(term.course_count(&course.name), 2);
+        assert_eq!(term.units, Credits::from(4));
+
+        let single_course: Course = Course::new(String::from("CS 10"), 4);
+        assert_eq!(term.add(&single_course), Ok(true));
+        assert_eq!(term.add(&single_course), Ok(false));
+
+        assert_eq!(term.course_count(&single_course.name), 1);
+        assert!(term.is_full());
+    }
+
+    #[test]
+    fn test_term_remove_course() {
+        let mut course: Course = Course::new(String::from("CS 199"), 2);
+        course.repeatable(2);
+
+        let mut term: Term = Term::new(&TermType::Fall, 8);
+        term.add(&course).unwrap();
+        term.add_units(&course, Credits::from(3)).unwrap();
+
+        assert_eq!(term.remove_course(&course.name), Some(Credits::from(3)));
+        assert_eq!(term.units, Credits::from(2));
+        assert_eq!(term.remove_course(&course.name), Some(Credits::from(2)));
+        assert_eq!(term.remove_course(&course.name), None);
+        assert!(term.is_empty());
+    }
+
+    #[test]
+    fn test_get_equivalents() {
+        let mut courses: Courses = Courses::new();
+        let first_course_name = String::from("CS 340");
+        let second_course_name = String::from("MATH 340");
+        let third_course_name = String::from("CS 240");
+
+        assert_eq!(courses.get_equivalents_for(&first_course_name), None);
+
+        courses.add_equivalence(&first_course_name, &second_course_name);
+        courses.add_equivalence(&second_course_name, &third_course_name);
+
+        let first_course_equivalents: HashSet<String> =
+            courses.get_equivalents_for(&first_course_name).unwrap();
+        assert_eq!(first_course_equivalents.len(), 2);
+        assert!(!first_course_equivalents.contains(&first_course_name));
+
+        assert_eq!(
+            courses.remove_equivalence(&second_course_name, &third_course_name),
+            Some((second_course_name.clone(), third_course_name.clone()))
+        );
+        assert_eq!(courses.get_equivalents_for(&third_course_name), None);
+    }
+
+    #[test]
+    fn test_get_term_with_equivalents() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("CS 340"), 4);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("MATH 340"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        let third_course: Course = Course::new(String::from("CS 440"), 4);
+        let third_course_name = third_course.name.to_string();
+
+        let old_course_name = String::from("CS 330");
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_course(third_course);
+
+        courses.add_equivalence(&first_course_name, &second_course_name);
+        courses.add_equivalence(&first_course_name, &old_course_name);
+        courses.add_prerequisite(&third_course_name, &old_course_name);
+
+        let result: Option<Vec<Term>> = courses.get_terms([8, 8, 8, 8]);
+        assert_ne!(result, None);
+
+        let completed_terms: Vec<Term> = result.unwrap();
+
+        for term in &completed_terms {
+            println!("{}", term);
+        }
+
+        let equivalent_count = completed_terms
+            .iter()
+            .filter(|x| x.has_course(&first_course_name) || x.has_course(&second_course_name))
+            .count();
+        assert_eq!(equivalent_count, 1);
+
+        assert_eq!(completed_terms.len(), 2);
+        assert!(completed_terms[1].has_course(&third_course_name));
+    }
+
+    #[test]
+    fn test_get_term_with_fractional_credits() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course =
+            Course::new(String::from("LAB 1"), Credits::from_hundredths(150));
+        let second_course: Course =
+            Course::new(String::from("LAB 2"), Credits::from_hundredths(150));
+        let third_course: Course =
+            Course::new(String::from("LAB 3"), Credits::from_hundredths(150));
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_course(third_course);
+
+        let result: Option<Vec<Term>> = courses.get_terms([3, 3, 3, 3]);
+        assert_ne!(result, None);
+
+        let completed_terms: Vec<Term> = result.unwrap();
+
+        for term in &completed_terms {
+            println!("{}", term);
+        }
+        assert_eq!(completed_terms.len(), 2);
+        assert!(completed_terms[0].is_full());
+    }
+
+    #[test]
+    fn test_term_add_overflow() {
+        let course: Course = Course::new(String::from("Test"), Credits::MAX);
+        let mut term: Term = Term::new(&TermType::Fall, Credits::MAX);
+
+        assert!(term.can_add_course(&course));
+        assert_eq!(term.add(&course), Ok(true));
+        assert!(!term.can_add_course(&Course::new(String::from("Test2"), 1)));
+        assert_eq!(
+            term.add(&Course::new(String::from("Test2"), 1)),
+            Err(CreditError::Overflow)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_courses() {
+        let mut courses: Courses = Courses::new();
+        let mut first_course: Course = Course::new(String::from("CS 10"), 4);
+        first_course.available_by(&TermType::Fall);
+        let first_course_name = first_course.name.to_string();
+
+        let mut second_course: Course = Course::new(String::from("CS 11"), 1);
+        second_course
+            .variable_credits(Credits::from_hundredths(250))
+            .repeatable(2);
+        let second_course_name = second_course.name.to_string();
+
+        let third_course: Course = Course::new(String::from("CS 11L"), 1);
+        let third_course_name = third_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_course(third_course);
+
+        courses.add_prerequisite(&second_course_name, &first_course_name);
+        courses
+            .add_concurrency(&second_course_name, &third_course_name)
+            .unwrap();
+        courses.add_equivalence(&first_course_name, "MATH 10");
+        courses.add_recommendation(&third_course_name, &first_course_name);
+        courses.prefer_term(&third_course_name, &TermType::Winter);
+
+        let serialized: String = serde_json::to_string(&courses).unwrap();
+        let deserialized: Courses = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized, courses);
+        assert_eq!(
+            deserialized.get_prerequisites(&third_course_name),
+            courses.get_prerequisites(&third_course_name)
+        );
+        assert_eq!(
+            deserialized.get_concurrents_for(&second_course_name),
+            courses.get_concurrents_for(&second_course_name)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_terms() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("CS 10"), 4);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 11"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_prerequisite(&second_course_name, &first_course_name);
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+
+        let serialized: String = serde_json::to_string(&completed_terms).unwrap();
+        let deserialized: Vec<Term> = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized, completed_terms);
+    }
+
+    #[test]
+    fn test_get_term_with_prerequisite_choice() {
+        let mut courses: Courses = Courses::new();
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Winter);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 105"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        let third_course: Course = Course::new(String::from("CS 201"), 4);
+        let third_course_name = third_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_course(third_course);
+
+        courses.add_prerequisite_choice(
+            &third_course_name,
+            &[first_course_name.clone(), second_course_name.clone()],
+        );
+        assert_eq!(
+            courses.get_prerequisite_choices(&third_course_name),
+            Some(vec![vec![
+                first_course_name.clone(),
+                second_course_name.clone()
+            ]])
+        );
+
+        let result: Option<Vec<Term>> = courses.get_terms([4, 4, 4, 4]);
+        assert_ne!(result, None);
+
+        let completed_terms: Vec<Term> = result.unwrap();
+
+        for term in &completed_terms {
+            println!("{}", term);
+        }
+
+        assert_eq!(completed_terms.len(), 3);
+        assert!(completed_terms[0].has_course(&second_course_name));
+        assert!(!completed_terms[0].has_course(&third_course_name));
+    }
+
+    #[test]
+    fn test_concurrents_with_existing_prerequisite_choice() {
+        let mut courses: Courses = Courses::new();
+        let first_course_name = String::from("CS 101");
+        let second_course_name = String::from("CS 105");
+        let third_course_name = String::from("CS 201");
+        let fourth_course_name = String::from("CS 201L");
+
+        courses.add_course(Course::new(first_course_name.clone(), 4));
+        courses.add_course(Course::new(second_course_name.clone(), 4));
+        courses.add_course(Course::new(third_course_name.clone(), 4));
+        courses.add_course(Course::new(fourth_course_name.clone(), 1));
+
+        let choice: Vec<String> = vec![first_course_name, second_course_name];
+        courses.add_prerequisite_choice(&third_course_name, &choice);
+        courses
+            .add_concurrency(&third_course_name, &fourth_course_name)
+            .unwrap();
+
+        assert_eq!(
+            courses.get_prerequisite_choices(&fourth_course_name),
+            Some(vec![choice])
+        );
+
+        let completed_terms: Vec<Term> = courses.get_terms([12, 12, 12, 12]).unwrap();
+        assert!(!completed_terms[0].has_course(&fourth_course_name));
+        assert!(completed_terms[1].has_course(&third_course_name));
+        assert!(completed_terms[1].has_course(&fourth_course_name));
+    }
+
+    #[test]
+    fn test_sequence_with_concurrents_rejected() {
+        let mut courses: Courses = Courses::new();
+        let first_course_name = String::from("CS 490A");
+        let second_course_name = String::from("CS 490B");
+        let lab_course_name = String::from("CS 490L");
+
+        courses.add_course(Course::new(first_course_name.clone(), 4));
+        courses.add_course(Course::new(second_course_name.clone(), 4));
+        courses.add_course(Course::new(lab_course_name.clone(), 1));
+
+        assert!(courses.add_sequence(&[first_course_name.clone(), second_course_name.clone()]));
+        assert_eq!(
+            courses.add_concurrency(&first_course_name, &lab_course_name),
+            Err(ConcurrentInSequenceError(first_course_name.clone()))
+        );
+        assert_eq!(courses.get_concurrents_for(&lab_course_name), None);
+
+        courses.remove_sequence(&first_course_name);
+        assert_eq!(
+            courses.add_concurrency(&first_course_name, &lab_course_name),
+            Ok(())
+        );
+        assert!(!courses.add_sequence(&[first_course_name.clone(), second_course_name]));
+        assert_eq!(courses.get_sequence_for(&first_course_name), None);
+    }
+
+    #[test]
+    fn test_get_term_unschedulable() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("CS 10"), 4);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 11"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+
+        courses.add_prerequisite(&first_course_name, &second_course_name);
+        courses.add_prerequisite(&second_course_name, &first_course_name);
+
+        assert_eq!(courses.get_terms([8, 8, 8, 8]), None);
+        assert_eq!(
+            courses.get_terms_with(&ScheduleOptions::new([8, 8, 8, 8])),
+            Err(ScheduleError::Unschedulable {
+                courses: vec![first_course_name, second_course_name]
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_term_credit_overflow() {
+        let mut courses: Courses = Courses::new();
+        let first_course_name = String::from("CS 10");
+        let second_course_name = String::from("CS 10L");
+
+        courses.add_course(Course::new(
+            first_course_name.clone(),
+            Credits::from_hundredths(u32::MAX - 100),
+        ));
+        courses.add_course(Course::new(second_course_name.clone(), 4));
+        courses
+            .add_concurrency(&first_course_name, &second_course_name)
+            .unwrap();
+
+        assert_eq!(
+            courses.get_terms_with(&ScheduleOptions::new([Credits::MAX; 4])),
+            Err(ScheduleError::CreditOverflow(CreditError::Overflow))
+        );
+        assert_eq!(
+            courses.get_terms_with(&ScheduleOptions::new([0, 0, 0, 0])),
+            Err(ScheduleError::Unschedulable {
+                courses: vec![first_course_name, second_course_name]
+            })
+        );
+        assert_eq!(
+            Courses::new().get_terms_with(&ScheduleOptions::new([8, 8, 8, 8])),
+            Err(ScheduleError::NoCourses)
+        );
+    }
+
+    #[test]
+    fn test_get_term_years() {
+        let mut courses: Courses = Courses::new();
+        let course_names: Vec<String> = (1..=5).map(|x| format!("CS {}", x)).collect();
+
+        for course_name in &course_names {
+            courses.add_course(Course::new(course_name.clone(), 4));
+        }
+
+        for pair in course_names.windows(2) {
+            courses.add_prerequisite(&pair[1], &pair[0]);
+        }
+
+        let completed_terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+        let term_years: Vec<(TermType, u16)> = completed_terms
+            .iter()
+            .map(|x| (x.term_type().clone(), x.year()))
+            .collect();
+
+        assert_eq!(
+            term_years,
+            vec![
+                (TermType::Fall, 1),
+                (TermType::Winter, 1),
+                (TermType::Spring, 1),
+                (TermType::Summer, 1),
+                (TermType::Fall, 2),
+            ]
+        );
+        assert_eq!(completed_terms[4].courses()[0].0, "CS 5");
+    }
+
+    #[test]
+    fn test_get_terms_with_options() {
+        let mut courses: Courses = Courses::new();
+        let first_course_name = String::from("CS 101");
+        let second_course_name = String::from("CS 201");
+        let third_course_name = String::from("CS 301");
+
+        courses.add_course(Course::new(first_course_name.clone(), 4));
+        courses.add_course(Course::new(second_course_name.clone(), 4));
+        courses.add_course(Course::new(third_course_name.clone(), 4));
+        courses.add_prerequisite(&second_course_name, &first_course_name);
+        courses.add_prerequisite(&third_course_name, &second_course_name);
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options
+            .start_at(&TermType::Summer)
+            .complete(&first_course_name);
+
+        let completed_terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        assert_eq!(completed_terms.len(), 2);
+        assert_eq!(completed_terms[0].term_type(), &TermType::Summer);
+        assert_eq!(completed_terms[0].year(), 1);
+        assert!(completed_terms[0].has_course(&second_course_name));
+        assert_eq!(completed_terms[1].term_type(), &TermType::Fall);
+        assert_eq!(completed_terms[1].year(), 2);
+        assert!(completed_terms[1].has_course(&third_course_name));
+        assert!(!completed_terms
+            .iter()
+            .any(|x| x.has_course(&first_course_name)));
+    }
+
+    #[test]
+    fn test_concurrents_with_new_prerequisite() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("Test1"), 3);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("Test2"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        let third_course: Course = Course::new(String::from("Test3"), 5);
+        let third_course_name = third_course.name.to_string();
+
+        let fourth_course: Course = Course::new(String::from("Test4"), 4);
+        let fourth_course_name = fourth_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_course(third_course);
+        courses.add_course(fourth_course);
+
+        courses
+            .add_concurrency(&first_course_name, &second_course_name)
+            .unwrap();
+        courses
+            .add_concurrency(&first_course_name, &third_course_name)
+            .unwrap();
+
+        let test1_concurrents_results = courses.get_concurrents_for(&first_course_name);
+        assert_ne!(test1_concurrents_results, None);
+
+        let test1_concurrents: (HashSet<String>, Result<Credits, CreditError>) =
+            test1_concurrents_results.unwrap();
+        assert_eq!(test1_concurrents.0.len(), 3);
+        assert_eq!(courses.get_prerequisites(&first_course_name), None);
+
+        courses.add_prerequisite(&first_course_name, &fourth_course_name);
+        assert_ne!(courses.get_prerequisites(&first_course_name), None);
+        assert_ne!(courses.get_prerequisites(&second_course_name), None);
+        assert_ne!(courses.get_prerequisites(&third_course_name), None);
+    }
+
+    #[test]
+    fn test_concurrents_with_existing_prerequisites() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("Test1"), 3);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("Test2"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        let third_course: Course = Course::new(String::from("Test3"), 5);
+        let third_course_name = third_course.name.to_string();
+
+        let fourth_course: Course = Course::new(String::from("Test4"), 4);
+        let fourth_course_name = fourth_course.name.to_string();
+
+        let fifth_course: Course = Course::new(String::from("Test5"), 3);
+        let fifth_course_name = fifth_course.name.to_string();
+
+        let sixth_course: Course = Course::new(String::from("Test6"), 4);
         let sixth_course_name = sixth_course.name.to_string();
 
         courses.add_course(first_course);
@@ -622,8 +2314,12 @@ mod tests {
         courses.add_course(third_course);
         courses.add_course(fourth_course);
 
-        courses.add_concurrency(&first_course_name, &second_course_name);
-        courses.add_concurrency(&third_course_name, &fourth_course_name);
+        courses
+            .add_concurrency(&first_course_name, &second_course_name)
+            .unwrap();
+        courses
+            .add_concurrency(&third_course_name, &fourth_course_name)
+            .unwrap();
         assert_ne!(courses.get_concurrents_for(&first_course_name), None);
         assert_ne!(courses.get_concurrents_for(&third_course_name), None);
 
@@ -632,7 +2328,9 @@ mod tests {
         assert_ne!(courses.get_prerequisites(&first_course_name), None);
         assert_ne!(courses.get_prerequisites(&third_course_name), None);
 
-        courses.add_concurrency(&second_course_name, &fourth_course_name);
+        courses
+            .add_concurrency(&second_course_name, &fourth_course_name)
+            .unwrap();
 
         let first_course_prerequisites_results: Option<HashSet<String>> =
             courses.get_prerequisites(&first_course_name);
@@ -1,3 +1,823 @@
-fn main() {
-    println!("Hello, world!");
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use cle_rust::catalog::{self, CatalogError, CsvColumns};
+use cle_rust::export::{self, GraphFocus, GraphOptions};
+use cle_rust::{Courses, Credits, Plan, ScheduleError, ScheduleOptions, Term, TermType};
+
+mod board;
+mod repl;
+
+use board::Board;
+use repl::Session;
+
+const TERM_TYPES: [TermType; 4] = [
+    TermType::Fall,
+    TermType::Winter,
+    TermType::Spring,
+    TermType::Summer,
+];
+
+/// Plans which terms to take the courses of a catalog in.
+#[derive(Parser)]
+#[command(name = "cle-rust", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Schedules every course of a catalog and prints the plan.
+    Plan {
+        /// A catalog file: .toml, .csv, .json as saved by the shell, or the
+        /// line-oriented format otherwise.
+        catalog: PathBuf,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+        #[arg(long, value_enum, default_value_t = PlanFormat::Text)]
+        format: PlanFormat,
+        /// Writes the plan to a file instead of printing it.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Checks a catalog for mistakes and courses that can never be planned,
+    /// or checks a plan against the rules of the catalog.
+    Validate {
+        catalog: PathBuf,
+        /// A plan saved by the board, or made elsewhere in the same format,
+        /// to check instead of the catalog.
+        #[arg(long)]
+        plan: Option<PathBuf>,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+    },
+    /// Draws the prerequisite graph of a catalog.
+    Graph {
+        catalog: PathBuf,
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+        /// Only draws this course and the courses related to it.
+        #[arg(long)]
+        focus: Option<String>,
+        /// Which related courses to draw with --focus.
+        #[arg(long, value_enum, default_value_t = Direction::Both)]
+        direction: Direction,
+        /// Colors each course by the term the plan places it in.
+        #[arg(long)]
+        color_by_plan: bool,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Shows the prerequisite chain of a course and the earliest term it can
+    /// be taken in.
+    Explain {
+        catalog: PathBuf,
+        course: String,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+    },
+    /// Builds and plans a catalog interactively, starting from a catalog file
+    /// when one is given.
+    Shell {
+        catalog: Option<PathBuf>,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+    },
+    /// Shows a plan as a board of terms to move courses between, listing the
+    /// rules the plan breaks after every move.
+    Board {
+        catalog: PathBuf,
+        /// The plan to edit and save to. The catalog is planned when the file
+        /// does not exist yet.
+        #[arg(long, default_value = "plan.json")]
+        plan: PathBuf,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+    },
+}
+
+#[derive(Args)]
+struct ScheduleArgs {
+    /// The term to plan from.
+    #[arg(long, default_value = "fall")]
+    start: TermType,
+    /// The unit limit of every term, or of Fall, Winter, Spring and Summer
+    /// separated by commas.
+    #[arg(long, default_value = "16", value_parser = parse_unit_limits)]
+    limits: [Credits; 4],
+    /// Courses already completed, separated by commas or given repeatedly.
+    #[arg(long, value_delimiter = ',')]
+    completed: Vec<String>,
+    /// A course to take in a given term of a given year, as "CS 490=Spring:2".
+    /// May be given repeatedly.
+    #[arg(long, value_parser = parse_pin)]
+    pin: Vec<(String, TermType, u16)>,
+}
+
+impl ScheduleArgs {
+    fn to_options(&self) -> ScheduleOptions {
+        let mut options = ScheduleOptions::new(self.limits);
+        options.start_at(&self.start);
+
+        for course in &self.completed {
+            options.complete(course.trim());
+        }
+
+        for (course, term, year) in &self.pin {
+            options.pin(course, term, *year);
+        }
+
+        options
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PlanFormat {
+    Text,
+    Json,
+    Markdown,
+    Html,
+    Latex,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Direction {
+    Ancestors,
+    Descendants,
+    Both,
+}
+
+fn parse_unit_limits(text: &str) -> Result<[Credits; 4], String> {
+    let limits: Vec<Credits> = text
+        .split(',')
+        .map(|x| x.trim().parse::<Credits>().map_err(|e| e.to_string()))
+        .collect::<Result<_, _>>()?;
+
+    match limits.as_slice() {
+        [limit] => Ok([*limit; 4]),
+        [fall, winter, spring, summer] => Ok([*fall, *winter, *spring, *summer]),
+        _ => Err(String::from(
+            "expected one limit, or four for Fall, Winter, Spring and Summer",
+        )),
+    }
+}
+
+fn parse_pin(text: &str) -> Result<(String, TermType, u16), String> {
+    let (course, term) = text
+        .rsplit_once('=')
+        .ok_or_else(|| String::from("expected a course and a term, as \"CS 490=Spring:2\""))?;
+    let (term_type, year) = term.split_once(':').unwrap_or((term, "1"));
+
+    let term_type: TermType = term_type.trim().parse().map_err(|e| format!("{}", e))?;
+    let year: u16 = year
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a year of the plan", year.trim()))?;
+
+    Ok((course.trim().to_string(), term_type, year))
+}
+
+/// A catalog along with the problems found that did not stop it loading.
+struct LoadedCatalog {
+    courses: Courses,
+    warnings: Vec<String>,
+}
+
+fn render_errors(errors: &[CatalogError], text: &str) -> String {
+    errors
+        .iter()
+        .map(|x| match x.columns {
+            Some(_) => x.snippet(text),
+            None => format!("{}\n", x),
+        })
+        .collect()
+}
+
+fn load_catalog(path: &Path) -> Result<LoadedCatalog, String> {
+    let text: String = fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    let extension: String = path
+        .extension()
+        .map(|x| x.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let catalog = match extension.as_str() {
+        "json" => {
+            return serde_json::from_str(&text)
+                .map(|courses| LoadedCatalog {
+                    courses,
+                    warnings: Vec::new(),
+                })
+                .map_err(|e| format!("{} could not be loaded: {}", path.display(), e))
+        }
+        "toml" => catalog::load_toml(&text).map(|courses| LoadedCatalog {
+            courses,
+            warnings: Vec::new(),
+        }),
+        "csv" => catalog::load_csv(&text, &CsvColumns::default())
+            .map(|import| LoadedCatalog {
+                courses: import.courses,
+                warnings: import
+                    .skipped_rows
+                    .iter()
+                    .chain(import.warnings.iter())
+                    .map(|x| x.to_string())
+                    .collect(),
+            })
+            .map_err(|e| vec![e]),
+        _ => catalog::load_dsl(&text).map(|courses| LoadedCatalog {
+            courses,
+            warnings: Vec::new(),
+        }),
+    };
+
+    catalog.map_err(|errors| {
+        format!(
+            "{} could not be loaded:\n{}",
+            path.display(),
+            render_errors(&errors, &text)
+        )
+    })
+}
+
+fn load_catalog_with_warnings(path: &Path) -> Result<Courses, String> {
+    let catalog = load_catalog(path)?;
+
+    for warning in &catalog.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    Ok(catalog.courses)
+}
+
+fn write_output(output: &Option<PathBuf>, text: &str) -> Result<(), String> {
+    match output {
+        Some(path) => {
+            fs::write(path, text).map_err(|e| format!("could not write {}: {}", path.display(), e))
+        }
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+fn plan_courses(courses: &Courses, options: &ScheduleOptions) -> Result<Plan, String> {
+    courses.get_plan_with(options).map_err(|e| match e {
+        ScheduleError::Pins(errors) => {
+            let errors: Vec<String> = errors.iter().map(|x| x.to_string()).collect();
+            format!(
+                "the catalog cannot be planned around its pins:\n  {}",
+                errors.join("\n  ")
+            )
+        }
+        e => format!("the catalog cannot be planned: {}", e),
+    })
+}
+
+fn plan_to_text(plan: &Plan) -> String {
+    let mut text = String::new();
+
+    for (index, term) in plan.terms().iter().enumerate() {
+        text.push_str(&format!(
+            "Term {} (year {}) - {}\n",
+            index + 1,
+            term.year(),
+            term
+        ));
+    }
+
+    if !plan.violations().is_empty() {
+        text.push_str("Preferences not followed:\n");
+
+        for violation in plan.violations() {
+            text.push_str(&format!("  {}\n", violation));
+        }
+    }
+
+    text
+}
+
+fn run_plan(
+    catalog: &Path,
+    schedule: &ScheduleArgs,
+    format: PlanFormat,
+    output: &Option<PathBuf>,
+) -> Result<ExitCode, String> {
+    let courses: Courses = load_catalog_with_warnings(catalog)?;
+    let plan: Plan = plan_courses(&courses, &schedule.to_options())?;
+
+    let text: String = match format {
+        PlanFormat::Text => plan_to_text(&plan),
+        PlanFormat::Json => export::plan_to_json(&courses, plan.terms()) + "\n",
+        PlanFormat::Markdown => export::plan_to_markdown(plan.terms()),
+        PlanFormat::Html => export::plan_to_html(plan.terms()),
+        PlanFormat::Latex => export::plan_to_latex(plan.terms()),
+    };
+
+    write_output(output, &text)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Finds the problems that keep courses of a catalog from being planned
+/// with the given options.
+fn lint_catalog(courses: &Courses, options: &ScheduleOptions) -> Vec<String> {
+    let mut problems: Vec<String> = Vec::new();
+    let mut reported_groups: HashSet<Vec<String>> = HashSet::new();
+
+    for course_name in courses.get_course_names() {
+        let course = courses.get_course(&course_name).unwrap();
+        // A term without units to spend holds no courses, so it does not
+        // count as offering the course.
+        let offered_terms: Vec<&TermType> = TERM_TYPES
+            .iter()
+            .filter(|x| course.is_available(x) && options.unit_limit(x) > Credits::ZERO)
+            .collect();
+
+        let largest_limit: Credits = match offered_terms.iter().map(|x| options.unit_limit(x)).max()
+        {
+            Some(x) => x,
+            None => {
+                problems.push(format!("{} is not offered in any term", course_name));
+                continue;
+            }
+        };
+
+        if course.credits > largest_limit {
+            problems.push(format!(
+                "{} is {} units, more than the {} units of any term it is offered in",
+                course_name, course.credits, largest_limit
+            ));
+        }
+
+        if let Some((group, units)) = courses.get_concurrents_for(&course_name) {
+            let mut group: Vec<String> = group.into_iter().collect();
+            group.sort();
+
+            let is_too_large: bool = units.as_ref().map_or(true, |x| *x > largest_limit);
+            if is_too_large && reported_groups.insert(group.clone()) {
+                problems.push(match units {
+                    Ok(units) => format!(
+                        "{} must be taken together for {} units, more than a term holds",
+                        group.join(", "),
+                        units
+                    ),
+                    Err(e) => format!("{} must be taken together: {}", group.join(", "), e),
+                });
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        if let Err(e) = courses.get_terms_with(options) {
+            problems.push(format!(
+                "the catalog cannot be planned, which usually means prerequisites form a cycle: {}",
+                e
+            ));
+        }
+    }
+
+    problems
+}
+
+fn load_plan(path: &Path) -> Result<Vec<Term>, String> {
+    let text: String = fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+
+    serde_json::from_str(&text)
+        .map_err(|e| format!("{} is not a saved plan: {}", path.display(), e))
+}
+
+fn run_validate(
+    catalog: &Path,
+    plan: &Option<PathBuf>,
+    schedule: &ScheduleArgs,
+) -> Result<ExitCode, String> {
+    let loaded_catalog = load_catalog(catalog)?;
+    let mut problems: Vec<String> = loaded_catalog.warnings;
+
+    match plan {
+        Some(path) => {
+            let terms: Vec<Term> = load_plan(path)?;
+            problems.extend(
+                loaded_catalog
+                    .courses
+                    .validate_terms_with(&terms, &schedule.to_options())
+                    .iter()
+                    .map(|x| x.to_string()),
+            );
+
+            if problems.is_empty() {
+                println!(
+                    "{}: {} terms, no problems found",
+                    path.display(),
+                    terms.len()
+                );
+                return Ok(ExitCode::SUCCESS);
+            }
+        }
+        None => {
+            problems.extend(lint_catalog(
+                &loaded_catalog.courses,
+                &schedule.to_options(),
+            ));
+
+            if problems.is_empty() {
+                println!(
+                    "{}: {} courses, no problems found",
+                    catalog.display(),
+                    loaded_catalog.courses.len()
+                );
+                return Ok(ExitCode::SUCCESS);
+            }
+        }
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+
+    Ok(ExitCode::FAILURE)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_graph(
+    catalog: &Path,
+    format: GraphFormat,
+    focus: &Option<String>,
+    direction: Direction,
+    color_by_plan: bool,
+    schedule: &ScheduleArgs,
+    output: &Option<PathBuf>,
+) -> Result<ExitCode, String> {
+    let courses: Courses = load_catalog_with_warnings(catalog)?;
+    let mut options = GraphOptions::new();
+
+    if let Some(course) = focus {
+        if courses.get_course(course).is_none() {
+            return Err(format!("unknown course '{}'", course));
+        }
+
+        let focus = match direction {
+            Direction::Ancestors => GraphFocus::Ancestors,
+            Direction::Descendants => GraphFocus::Descendants,
+            Direction::Both => GraphFocus::Both,
+        };
+        options.focus_on(course, focus);
+    }
+
+    if color_by_plan {
+        let plan: Plan = plan_courses(&courses, &schedule.to_options())?;
+        options.color_by_terms(plan.terms());
+    }
+
+    let text: String = match format {
+        GraphFormat::Dot => export::prerequisites_to_dot(&courses, &options),
+        GraphFormat::Mermaid => export::prerequisites_to_mermaid(&courses, &options),
+    };
+
+    write_output(output, &text)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Writes the prerequisites of a course as an indented tree, skipping any
+/// course already on the path to avoid following a cycle forever.
+fn push_prerequisite_tree(
+    courses: &Courses,
+    course: &String,
+    depth: usize,
+    path: &mut Vec<String>,
+    text: &mut String,
+) {
+    if path.contains(course) {
+        return;
+    }
+    path.push(course.clone());
+
+    let indent: String = "  ".repeat(depth);
+
+    let mut prerequisites: Vec<String> = courses
+        .get_prerequisites(course)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    prerequisites.sort();
+
+    for prerequisite in &prerequisites {
+        text.push_str(&format!("{}{}\n", indent, prerequisite));
+        push_prerequisite_tree(courses, prerequisite, depth + 1, path, text);
+    }
+
+    for choice in courses.get_prerequisite_choices(course).unwrap_or_default() {
+        text.push_str(&format!("{}one of {}\n", indent, choice.join(" or ")));
+
+        for option in &choice {
+            push_prerequisite_tree(courses, option, depth + 1, path, text);
+        }
+    }
+
+    path.pop();
+}
+
+/// Describes a course along with its prerequisite chain and the earliest term
+/// it can be taken in with the given options.
+fn explain_course(
+    courses: &Courses,
+    course_name: &str,
+    options: &ScheduleOptions,
+) -> Result<String, String> {
+    let course = courses
+        .get_course(course_name)
+        .ok_or_else(|| format!("unknown course '{}'", course_name))?;
+    let course_name: String = course.name.clone();
+
+    let offered_terms: Vec<String> = TERM_TYPES
+        .iter()
+        .filter(|x| course.is_available(x))
+        .map(|x| x.to_string())
+        .collect();
+
+    let mut text: String = format!(
+        "{} ({} units), offered {}\n",
+        course_name,
+        course.credits,
+        offered_terms.join(", ")
+    );
+
+    let mut prerequisite_tree = String::new();
+    push_prerequisite_tree(
+        courses,
+        &course_name,
+        1,
+        &mut Vec::new(),
+        &mut prerequisite_tree,
+    );
+
+    if prerequisite_tree.is_empty() {
+        text.push_str("No prerequisites\n");
+    } else {
+        text.push_str("Prerequisites:\n");
+        text.push_str(&prerequisite_tree);
+    }
+
+    if let Some(chain) = courses
+        .get_longest_prerequisite_chain(&course_name)
+        .filter(|x| x.len() > 2)
+    {
+        text.push_str(&format!(
+            "Longest prerequisite chain: {}\n",
+            chain.join(" -> ")
+        ));
+    }
+
+    let mut dependents: Vec<String> = courses.get_dependents(&course_name).into_iter().collect();
+    dependents.sort();
+    if !dependents.is_empty() {
+        text.push_str(&format!("Unlocks {}\n", dependents.join(", ")));
+    }
+
+    if let Some((concurrents, units)) = courses.get_concurrents_for(&course_name) {
+        let mut concurrents: Vec<String> = concurrents
+            .into_iter()
+            .filter(|x| *x != course_name)
+            .collect();
+        concurrents.sort();
+
+        match units {
+            Ok(units) => text.push_str(&format!(
+                "Taken together with {} for {} units\n",
+                concurrents.join(", "),
+                units
+            )),
+            Err(e) => text.push_str(&format!(
+                "Taken together with {}, but {}\n",
+                concurrents.join(", "),
+                e
+            )),
+        }
+    }
+
+    if options.is_completed(&course_name) {
+        text.push_str("Already completed\n");
+    } else {
+        match courses.get_earliest_terms(options).get(&course_name) {
+            Some(earliest) => text.push_str(&format!("Earliest term: {}\n", earliest)),
+            None => text.push_str("Cannot be taken\n"),
+        }
+    }
+
+    Ok(text)
+}
+
+fn run_explain(
+    catalog: &Path,
+    course_name: &str,
+    schedule: &ScheduleArgs,
+) -> Result<ExitCode, String> {
+    let courses: Courses = load_catalog_with_warnings(catalog)?;
+
+    print!(
+        "{}",
+        explain_course(&courses, course_name, &schedule.to_options())?
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_shell(catalog: &Option<PathBuf>, schedule: &ScheduleArgs) -> Result<ExitCode, String> {
+    let courses: Courses = match catalog {
+        Some(path) => load_catalog_with_warnings(path)?,
+        None => Courses::new(),
+    };
+
+    repl::run(Session::new(
+        courses,
+        schedule.to_options(),
+        catalog.clone(),
+    ))
+}
+
+fn run_board(catalog: &Path, plan: &Path, schedule: &ScheduleArgs) -> Result<ExitCode, String> {
+    let courses: Courses = load_catalog_with_warnings(catalog)?;
+    let options: ScheduleOptions = schedule.to_options();
+
+    let terms: Vec<Term> = match plan.exists() {
+        true => load_plan(plan)?,
+        false => plan_courses(&courses, &options)?.into_terms(),
+    };
+
+    board::run(Board::new(courses, options, terms, plan.to_path_buf()))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Command::Plan {
+            catalog,
+            schedule,
+            format,
+            output,
+        } => run_plan(catalog, schedule, *format, output),
+        Command::Validate {
+            catalog,
+            plan,
+            schedule,
+        } => run_validate(catalog, plan, schedule),
+        Command::Graph {
+            catalog,
+            format,
+            focus,
+            direction,
+            color_by_plan,
+            schedule,
+            output,
+        } => run_graph(
+            catalog,
+            *format,
+            focus,
+            *direction,
+            *color_by_plan,
+            schedule,
+            output,
+        ),
+        Command::Explain {
+            catalog,
+            course,
+            schedule,
+        } => run_explain(catalog, course, schedule),
+        Command::Shell { catalog, schedule } => run_shell(catalog, schedule),
+        Command::Board {
+            catalog,
+            plan,
+            schedule,
+        } => run_board(catalog, plan, schedule),
+    };
+
+    match result {
+        Ok(exit_code) => exit_code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unit_limits() {
+        assert_eq!(parse_unit_limits("12"), Ok([Credits::from(12); 4]));
+        assert_eq!(
+            parse_unit_limits("12, 12, 16, 8"),
+            Ok([
+                Credits::from(12),
+                Credits::from(12),
+                Credits::from(16),
+                Credits::from(8)
+            ])
+        );
+        assert!(parse_unit_limits("12,12").is_err());
+        assert!(parse_unit_limits("twelve").is_err());
+    }
+
+    #[test]
+    fn test_parse_pin() {
+        assert_eq!(
+            parse_pin("CS 490 = spring:2"),
+            Ok((String::from("CS 490"), TermType::Spring, 2))
+        );
+        assert_eq!(
+            parse_pin("CS 101=Fall"),
+            Ok((String::from("CS 101"), TermType::Fall, 1))
+        );
+        assert!(parse_pin("CS 101").is_err());
+        assert!(parse_pin("CS 101=Fall:first").is_err());
+    }
+
+    #[test]
+    fn test_lint_catalog() {
+        let courses: Courses = catalog::load_dsl(
+            "CS 101 (4) [Fall]
+CS 201 (4) requires CS 101
+CS 290 (20) [Fall]
+CS 301 (8) [Fall] with CS 301L
+CS 301L (8) [Fall]
+CS 390 (4) [Winter]
+",
+        )
+        .unwrap();
+
+        let problems: Vec<String> = lint_catalog(&courses, &ScheduleOptions::new([12, 0, 12, 12]));
+        assert_eq!(
+            problems,
+            vec![
+                String::from(
+                    "CS 290 is 20 units, more than the 12 units of any term it is offered in"
+                ),
+                String::from(
+                    "CS 301, CS 301L must be taken together for 16 units, more than a term holds"
+                ),
+                String::from("CS 390 is not offered in any term"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prerequisite_tree() {
+        let courses: Courses = catalog::load_dsl(
+            "CS 101 (4)
+CS 105 (4)
+CS 201 (4) requires CS 101 or CS 105
+CS 301 (4) requires CS 201
+",
+        )
+        .unwrap();
+
+        let mut text = String::new();
+        push_prerequisite_tree(
+            &courses,
+            &String::from("CS 301"),
+            0,
+            &mut Vec::new(),
+            &mut text,
+        );
+        assert_eq!(text, "CS 201\n  one of CS 101 or CS 105\n");
+    }
+
+    #[test]
+    fn test_explain_course_earliest_term() {
+        let courses: Courses = catalog::load_dsl(
+            "CS 101 (4) [Fall]
+CS 105 (4) [Fall]
+CS 201 (4) requires CS 101
+",
+        )
+        .unwrap();
+        let options = ScheduleOptions::new([4, 4, 4, 4]);
+
+        // Only one course fits in a term, so the plan takes one of these a
+        // year later than it could be taken.
+        for course_name in ["CS 101", "CS 105"] {
+            let text: String = explain_course(&courses, course_name, &options).unwrap();
+            assert!(text.ends_with("Earliest term: Fall, year 1\n"));
+        }
+
+        let text: String = explain_course(&courses, "CS 201", &options).unwrap();
+        assert!(text.ends_with("Earliest term: Winter, year 1\n"));
+    }
 }
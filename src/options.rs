@@ -0,0 +1,99 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Credits, TermType, TracedTerm};
+
+/// How `Courses::get_terms_with` schedules a catalog: the unit limit of each
+/// term type, the term to start from, the courses already completed, which
+/// are left out of the plan but count towards prerequisites, and the courses
+/// pinned to a term.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduleOptions {
+    pub(crate) unit_limits: [Credits; 4],
+    pub(crate) start_term: TermType,
+    pub(crate) completed_courses: HashSet<String>,
+    pub(crate) pinned_courses: HashMap<String, TracedTerm>,
+    /// The order the scheduler considers the courses of a term in, which is
+    /// the catalog's own order when empty.
+    pub(crate) course_order: Vec<String>,
+    pub(crate) is_traced: bool,
+}
+
+impl ScheduleOptions {
+    /// Creates options that start scheduling in Fall with nothing completed,
+    /// like `Courses::get_terms`.
+    pub fn new(unit_limits: [impl Into<Credits>; 4]) -> ScheduleOptions {
+        ScheduleOptions {
+            unit_limits: unit_limits.map(|x| x.into()),
+            start_term: TermType::Fall,
+            completed_courses: HashSet::new(),
+            pinned_courses: HashMap::new(),
+            course_order: Vec::new(),
+            is_traced: false,
+        }
+    }
+
+    /// Changes the unit limit of each term type, keeping every other option.
+    pub fn set_unit_limits(
+        &mut self,
+        unit_limits: [impl Into<Credits>; 4],
+    ) -> &mut ScheduleOptions {
+        self.unit_limits = unit_limits.map(|x| x.into());
+        self
+    }
+
+    pub fn start_at(&mut self, term: &TermType) -> &mut ScheduleOptions {
+        self.start_term = term.clone();
+        self
+    }
+
+    pub fn complete(&mut self, course: &str) -> &mut ScheduleOptions {
+        self.completed_courses.insert(course.to_string());
+        self
+    }
+
+    /// Requires taking the course in the term of the given year of the plan,
+    /// where year 1 begins with the start term. Its concurrent courses are
+    /// taken with it, and the rest of its sequence in the terms around it.
+    pub fn pin(&mut self, course: &str, term: &TermType, year: u16) -> &mut ScheduleOptions {
+        self.pinned_courses.insert(
+            course.to_string(),
+            TracedTerm {
+                term: term.clone(),
+                year,
+            },
+        );
+        self
+    }
+
+    /// Records why each course is planned in the term it is, which
+    /// `Plan::trace` gives afterwards.
+    pub fn record_trace(&mut self) -> &mut ScheduleOptions {
+        self.is_traced = true;
+        self
+    }
+
+    pub fn unit_limit(&self, term: &TermType) -> Credits {
+        self.unit_limits[term.clone() as usize]
+    }
+
+    pub fn start_term(&self) -> &TermType {
+        &self.start_term
+    }
+
+    pub fn is_completed(&self, course: &str) -> bool {
+        self.completed_courses.contains(course)
+    }
+
+    pub fn completed_courses(&self) -> &HashSet<String> {
+        &self.completed_courses
+    }
+
+    pub fn pinned_term(&self, course: &str) -> Option<&TracedTerm> {
+        self.pinned_courses.get(course)
+    }
+
+    pub fn is_traced(&self) -> bool {
+        self.is_traced
+    }
+}
@@ -0,0 +1,553 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::timing::get_term_offset;
+use crate::{Courses, CreditError, Credits, ScheduleOptions, TracedTerm};
+
+/// A reason the courses pinned by the options can not be planned where they
+/// are pinned. Courses pinned along with another, such as its concurrent
+/// courses, count as pinned too.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PinError {
+    UnknownCourse {
+        course: String,
+    },
+    AlreadyCompleted {
+        course: String,
+    },
+    /// The course is pinned to a term before the term the plan starts at.
+    BeforeStart {
+        course: String,
+        term: TracedTerm,
+    },
+    /// The course is pinned to one term by itself and to another through a
+    /// course it is concurrent with or in a sequence with.
+    ConflictingPins {
+        course: String,
+        term: TracedTerm,
+        other_term: TracedTerm,
+    },
+    NotOffered {
+        course: String,
+        term: TracedTerm,
+    },
+    /// The course can not earn its target credits within the one term it is
+    /// pinned to, even when repeated.
+    TargetCreditsNotInOneTerm {
+        course: String,
+        term: TracedTerm,
+        target_credits: Credits,
+    },
+    /// The prerequisite is pinned to the same term or later, or can not be
+    /// taken before the term at all.
+    PrerequisiteNotBefore {
+        course: String,
+        term: TracedTerm,
+        prerequisite: String,
+    },
+    PrerequisiteChoiceNotBefore {
+        course: String,
+        term: TracedTerm,
+        options: Vec<String>,
+    },
+    UnitLimitExceeded {
+        term: TracedTerm,
+        units: Credits,
+        unit_limit: Credits,
+    },
+    /// The units pinned to the term are too large to count.
+    UnitsOverflow {
+        term: TracedTerm,
+    },
+}
+
+impl fmt::Display for PinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PinError::UnknownCourse { course } => {
+                write!(f, "{} is pinned but is not in the catalog", course)
+            }
+            PinError::AlreadyCompleted { course } => {
+                write!(f, "{} is pinned but is already completed", course)
+            }
+            PinError::BeforeStart { course, term } => write!(
+                f,
+                "{} is pinned to {}, before the plan starts",
+                course, term
+            ),
+            PinError::ConflictingPins {
+                course,
+                term,
+                other_term,
+            } => write!(
+                f,
+                "{} is pinned to both {} and {}",
+                course, term, other_term
+            ),
+            PinError::NotOffered { course, term } => write!(
+                f,
+                "{} is pinned to {}, but is not offered in {}",
+                course, term, term.term
+            ),
+            PinError::TargetCreditsNotInOneTerm {
+                course,
+                term,
+                target_credits,
+            } => write!(
+                f,
+                "{} is pinned to {}, but its {} target units take more than one term",
+                course, term, target_credits
+            ),
+            PinError::PrerequisiteNotBefore {
+                course,
+                term,
+                prerequisite,
+            } => write!(
+                f,
+                "{} is pinned to {}, but {} cannot be taken before it",
+                course, term, prerequisite
+            ),
+            PinError::PrerequisiteChoiceNotBefore {
+                course,
+                term,
+                options,
+            } => write!(
+                f,
+                "{} is pinned to {}, but none of {} can be taken before it",
+                course,
+                term,
+                options.join(", ")
+            ),
+            PinError::UnitLimitExceeded {
+                term,
+                units,
+                unit_limit,
+            } => write!(
+                f,
+                "{} has {} pinned units, over its limit of {}",
+                term, units, unit_limit
+            ),
+            PinError::UnitsOverflow { term } => {
+                write!(f, "{} has too many pinned units to count", term)
+            }
+        }
+    }
+}
+
+impl Error for PinError {}
+
+impl Courses {
+    /// Gets the term each pinned course is taken in, along with its
+    /// concurrent courses and the rest of its sequence.
+    pub(crate) fn get_pinned_terms(
+        &self,
+        options: &ScheduleOptions,
+        errors: &mut Vec<PinError>,
+    ) -> HashMap<String, TracedTerm> {
+        let mut pinned_terms: HashMap<String, TracedTerm> = HashMap::new();
+        let start_index: usize = options.start_term.clone() as usize;
+
+        let mut pinned_courses: Vec<(&String, &TracedTerm)> =
+            options.pinned_courses.iter().collect();
+        pinned_courses.sort_by(|x, y| x.0.cmp(y.0));
+
+        for (course_name, term) in pinned_courses {
+            if !self.master_list.contains_key(course_name) {
+                errors.push(PinError::UnknownCourse {
+                    course: course_name.clone(),
+                });
+                continue;
+            } else if options.is_completed(course_name) {
+                errors.push(PinError::AlreadyCompleted {
+                    course: course_name.clone(),
+                });
+                continue;
+            }
+
+            let is_before_start: bool =
+                term.year == 0 || (term.year == 1 && (term.term.clone() as usize) < start_index);
+            let offset: usize = get_term_offset(&options.start_term, &term.term, term.year);
+
+            let group: Vec<(String, usize)> = match self.get_sequence_for(course_name) {
+                Some(sequence) => {
+                    let position: usize = sequence.iter().position(|x| x == course_name).unwrap();
+
+                    if is_before_start || offset < position {
+                        errors.push(PinError::BeforeStart {
+                            course: course_name.clone(),
+                            term: term.clone(),
+                        });
+                        continue;
+                    }
+
+                    sequence
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, x)| (x, offset + index - position))
+                        .collect()
+                }
+                None if is_before_start => {
+                    errors.push(PinError::BeforeStart {
+                        course: course_name.clone(),
+                        term: term.clone(),
+                    });
+                    continue;
+                }
+                None => match self.get_concurrents_for(course_name) {
+                    Some((concurrents, _)) => {
+                        let mut concurrents: Vec<String> = concurrents.into_iter().collect();
+                        concurrents.sort();
+                        concurrents.into_iter().map(|x| (x, offset)).collect()
+                    }
+                    None => vec![(course_name.clone(), offset)],
+                },
+            };
+
+            for (group_course_name, group_offset) in group {
+                let group_term: TracedTerm = self.get_term_at(&options.start_term, group_offset);
+
+                match pinned_terms.get(&group_course_name) {
+                    Some(other_term) if *other_term != group_term => {
+                        errors.push(PinError::ConflictingPins {
+                            course: group_course_name,
+                            term: group_term,
+                            other_term: other_term.clone(),
+                        });
+                    }
+                    _ => {
+                        pinned_terms.insert(group_course_name, group_term);
+                    }
+                }
+            }
+        }
+
+        pinned_terms
+    }
+
+    /// Gets the units each term has to keep free for the courses pinned to
+    /// it, which take all of their target credits there.
+    pub(crate) fn get_pinned_units(
+        &self,
+        pinned_terms: &HashMap<String, TracedTerm>,
+    ) -> Result<HashMap<TracedTerm, Credits>, CreditError> {
+        let mut pinned_units: HashMap<TracedTerm, Credits> = HashMap::new();
+
+        for (course_name, term) in pinned_terms {
+            let units: Credits = self.master_list[course_name].get_single_term_units();
+            let term_units = pinned_units.entry(term.clone()).or_insert(Credits::ZERO);
+            *term_units = term_units.checked_add(units)?;
+        }
+
+        Ok(pinned_units)
+    }
+
+    /// Checks the courses pinned by the options against the catalog, listing
+    /// every pin that contradicts its availability, prerequisites,
+    /// concurrent courses, sequences or the unit limits. A pinned course
+    /// earns all of its target credits in its term. Scheduling with pins
+    /// that pass these checks can still fail when the prerequisites of a
+    /// pinned course do not fit into the terms before it.
+    pub fn check_pins(&self, options: &ScheduleOptions) -> Vec<PinError> {
+        let mut errors: Vec<PinError> = Vec::new();
+        let pinned_terms: HashMap<String, TracedTerm> = self.get_pinned_terms(options, &mut errors);
+
+        let mut completed_courses: HashSet<String> = options.completed_courses.clone();
+        for course_name in &options.completed_courses {
+            completed_courses.extend(self.get_equivalents_for(course_name).unwrap_or_default());
+        }
+
+        let earliest_terms: HashMap<String, TracedTerm> = self.get_earliest_terms(options);
+        let get_offset = |course_name: &String| -> Option<usize> {
+            pinned_terms
+                .get(course_name)
+                .or_else(|| earliest_terms.get(course_name))
+                .map(|x| get_term_offset(&options.start_term, &x.term, x.year))
+        };
+        let is_taken_before = |course_name: &String, offset: usize| {
+            completed_courses.contains(course_name)
+                || get_offset(course_name).is_some_and(|x| x < offset)
+        };
+
+        let mut pinned_courses: Vec<(&String, &TracedTerm)> = pinned_terms.iter().collect();
+        pinned_courses.sort_by(|x, y| x.0.cmp(y.0));
+
+        let mut pinned_units: BTreeMap<usize, (TracedTerm, Result<Credits, CreditError>)> =
+            BTreeMap::new();
+
+        for (course_name, term) in pinned_courses {
+            let course = &self.master_list[course_name];
+            let offset: usize = get_term_offset(&options.start_term, &term.term, term.year);
+
+            if !course.is_available(&term.term) {
+                errors.push(PinError::NotOffered {
+                    course: course_name.clone(),
+                    term: term.clone(),
+                });
+            }
+
+            let mut prerequisites: Vec<String> = self
+                .get_prerequisites(course_name)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            prerequisites.sort();
+
+            errors.extend(
+                prerequisites
+                    .into_iter()
+                    .filter(|x| !is_taken_before(x, offset))
+                    .map(|x| PinError::PrerequisiteNotBefore {
+                        course: course_name.clone(),
+                        term: term.clone(),
+                        prerequisite: x,
+                    }),
+            );
+
+            for choice in self
+                .get_prerequisite_choices(course_name)
+                .unwrap_or_default()
+            {
+                if !choice.iter().any(|x| is_taken_before(x, offset)) {
+                    errors.push(PinError::PrerequisiteChoiceNotBefore {
+                        course: course_name.clone(),
+                        term: term.clone(),
+                        options: choice,
+                    });
+                }
+            }
+
+            let units: Credits = course.get_single_term_units();
+            if units < course.target_credits {
+                errors.push(PinError::TargetCreditsNotInOneTerm {
+                    course: course_name.clone(),
+                    term: term.clone(),
+                    target_credits: course.target_credits,
+                });
+            }
+
+            let term_units = pinned_units
+                .entry(offset)
+                .or_insert((term.clone(), Ok(Credits::ZERO)));
+            term_units.1 = term_units.1.clone().and_then(|x| x.checked_add(units));
+        }
+
+        for (term, units) in pinned_units.into_values() {
+            let unit_limit: Credits = options.unit_limit(&term.term);
+
+            match units {
+                Ok(units) if units > unit_limit => errors.push(PinError::UnitLimitExceeded {
+                    term,
+                    units,
+                    unit_limit,
+                }),
+                Ok(_) => {}
+                Err(_) => errors.push(PinError::UnitsOverflow { term }),
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Course, ScheduleError, Term, TermType};
+
+    fn traced_term(term: TermType, year: u16) -> TracedTerm {
+        TracedTerm { term, year }
+    }
+
+    fn find_term(terms: &[Term], course_name: &str) -> Option<TracedTerm> {
+        terms
+            .iter()
+            .find(|x| x.has_course(course_name))
+            .map(|x| traced_term(x.term_type().clone(), x.year()))
+    }
+
+    #[test]
+    fn test_schedule_with_pins() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("CS 201L"), 2));
+        courses.add_course(Course::new(String::from("CS 490"), 4));
+        courses.add_course(Course::new(String::from("CS 491"), 4));
+        courses.add_course(Course::new(String::from("ART 1"), 4));
+        courses.add_course(Course::new(String::from("ART 2"), 4));
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_sequence(&[String::from("CS 490"), String::from("CS 491")]);
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options
+            .pin("ART 2", &TermType::Spring, 2)
+            .pin("CS 201L", &TermType::Winter, 1)
+            .pin("CS 491", &TermType::Fall, 2);
+        assert!(courses.check_pins(&options).is_empty());
+
+        let terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        assert!(courses.validate_terms_with(&terms, &options).is_empty());
+
+        assert_eq!(
+            find_term(&terms, "ART 2"),
+            Some(traced_term(TermType::Spring, 2))
+        );
+        assert_eq!(
+            find_term(&terms, "CS 201"),
+            Some(traced_term(TermType::Winter, 1))
+        );
+        assert_eq!(
+            find_term(&terms, "CS 101"),
+            Some(traced_term(TermType::Fall, 1))
+        );
+        assert_eq!(
+            find_term(&terms, "CS 490"),
+            Some(traced_term(TermType::Summer, 1))
+        );
+        assert_eq!(
+            find_term(&terms, "CS 491"),
+            Some(traced_term(TermType::Fall, 2))
+        );
+    }
+
+    #[test]
+    fn test_check_pins() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("CS 201L"), 2));
+        courses.add_course(Course::new(String::from("CS 490"), 4));
+        courses.add_course(Course::new(String::from("CS 491"), 4));
+        courses.add_course(Course::new(String::from("ART 1"), 4));
+        courses.add_course(Course::new(String::from("ART 2"), 4));
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_sequence(&[String::from("CS 490"), String::from("CS 491")]);
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options
+            .start_at(&TermType::Winter)
+            .pin("CS 101", &TermType::Spring, 1)
+            .pin("CS 201", &TermType::Winter, 1)
+            .pin("CS 201L", &TermType::Spring, 1)
+            .pin("CS 491", &TermType::Winter, 1)
+            .pin("ART 1", &TermType::Fall, 1)
+            .pin("ART 2", &TermType::Winter, 1);
+
+        assert_eq!(
+            courses.check_pins(&options),
+            vec![
+                PinError::BeforeStart {
+                    course: String::from("ART 1"),
+                    term: traced_term(TermType::Fall, 1),
+                },
+                PinError::ConflictingPins {
+                    course: String::from("CS 201"),
+                    term: traced_term(TermType::Spring, 1),
+                    other_term: traced_term(TermType::Winter, 1),
+                },
+                PinError::ConflictingPins {
+                    course: String::from("CS 201L"),
+                    term: traced_term(TermType::Spring, 1),
+                    other_term: traced_term(TermType::Winter, 1),
+                },
+                PinError::BeforeStart {
+                    course: String::from("CS 491"),
+                    term: traced_term(TermType::Winter, 1),
+                },
+                PinError::NotOffered {
+                    course: String::from("CS 101"),
+                    term: traced_term(TermType::Spring, 1),
+                },
+                PinError::PrerequisiteNotBefore {
+                    course: String::from("CS 201"),
+                    term: traced_term(TermType::Winter, 1),
+                    prerequisite: String::from("CS 101"),
+                },
+                PinError::PrerequisiteNotBefore {
+                    course: String::from("CS 201L"),
+                    term: traced_term(TermType::Winter, 1),
+                    prerequisite: String::from("CS 101"),
+                },
+                PinError::UnitLimitExceeded {
+                    term: traced_term(TermType::Winter, 1),
+                    units: Credits::from(10),
+                    unit_limit: Credits::from(8),
+                },
+            ]
+        );
+        assert_eq!(
+            courses.get_terms_with(&options),
+            Err(ScheduleError::Pins(courses.check_pins(&options)))
+        );
+    }
+
+    #[test]
+    fn test_pins_reserve_units() {
+        let mut courses: Courses = Courses::new();
+        courses.add_course(Course::new(String::from("ART 1"), 4));
+        courses.add_course(Course::new(String::from("CS 490"), 8));
+        courses.add_course(Course::new(String::from("CS 491"), 8));
+        courses.add_sequence(&[String::from("CS 490"), String::from("CS 491")]);
+
+        // Starting the sequence in Fall would fill Winter before ART 1 is
+        // placed there.
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options.pin("ART 1", &TermType::Winter, 1);
+
+        let terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        assert_eq!(
+            find_term(&terms, "ART 1"),
+            Some(traced_term(TermType::Winter, 1))
+        );
+        assert_eq!(
+            find_term(&terms, "CS 490"),
+            Some(traced_term(TermType::Spring, 1))
+        );
+    }
+
+    #[test]
+    fn test_pin_repeatable_course() {
+        let mut courses: Courses = Courses::new();
+        let mut repeated_course: Course = Course::new(String::from("CS 199"), 2);
+        repeated_course.repeatable(3).set_target_credits(6);
+        courses.add_course(repeated_course);
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options.pin("CS 199", &TermType::Winter, 1);
+
+        let terms: Vec<Term> = courses.get_terms_with(&options).unwrap();
+        assert_eq!(terms.len(), 1);
+        assert_eq!(terms[0].course_count("CS 199"), 3);
+        assert_eq!(terms[0].units(), Credits::from(6));
+
+        let mut repeated_course: Course = Course::new(String::from("CS 199"), 2);
+        repeated_course.repeatable(3).set_target_credits(8);
+        courses.add_course(repeated_course);
+        assert_eq!(
+            courses.get_terms_with(&options),
+            Err(ScheduleError::Pins(vec![
+                PinError::TargetCreditsNotInOneTerm {
+                    course: String::from("CS 199"),
+                    term: traced_term(TermType::Winter, 1),
+                    target_credits: Credits::from(8),
+                }
+            ]))
+        );
+    }
+}
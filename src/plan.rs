@@ -0,0 +1,342 @@
+use std::fmt;
+
+use crate::{Courses, ScheduleTrace, Term, TermType};
+
+/// A soft preference that a plan does not follow. Terms are indexes into the
+/// plan's terms.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SoftViolation {
+    RecommendationNotMet {
+        course: String,
+        term: usize,
+        recommended: String,
+        recommended_term: Option<usize>,
+    },
+    PreferredTermMissed {
+        course: String,
+        term: usize,
+        preferred: TermType,
+        planned: TermType,
+        is_offered: bool,
+    },
+}
+
+impl SoftViolation {
+    pub fn course(&self) -> &str {
+        match self {
+            SoftViolation::RecommendationNotMet { course, .. } => course,
+            SoftViolation::PreferredTermMissed { course, .. } => course,
+        }
+    }
+
+    pub fn term(&self) -> usize {
+        match self {
+            SoftViolation::RecommendationNotMet { term, .. } => *term,
+            SoftViolation::PreferredTermMissed { term, .. } => *term,
+        }
+    }
+}
+
+impl fmt::Display for SoftViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoftViolation::RecommendationNotMet {
+                course,
+                term,
+                recommended,
+                recommended_term,
+            } => match recommended_term {
+                Some(x) => write!(
+                    f,
+                    "{} is planned in term {}, but its recommended course {} is not planned until term {}",
+                    course,
+                    term + 1,
+                    recommended,
+                    x + 1
+                ),
+                None => write!(
+                    f,
+                    "{} is planned in term {}, but its recommended course {} is not in the plan",
+                    course,
+                    term + 1,
+                    recommended
+                ),
+            },
+            SoftViolation::PreferredTermMissed {
+                course,
+                term,
+                preferred,
+                planned,
+                is_offered,
+            } => {
+                write!(
+                    f,
+                    "{} is planned for {} in term {} instead of its preferred {}",
+                    course,
+                    planned,
+                    term + 1,
+                    preferred
+                )?;
+
+                if !is_offered {
+                    write!(f, ", which it is not offered in")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The terms of a schedule along with the soft preferences it does not
+/// follow.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plan {
+    terms: Vec<Term>,
+    violations: Vec<SoftViolation>,
+    preferences_count: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    trace: Option<ScheduleTrace>,
+}
+
+impl Plan {
+    pub(crate) fn new(courses: &Courses, terms: Vec<Term>) -> Plan {
+        let mut violations: Vec<SoftViolation> = Vec::new();
+        let mut preferences_count = 0;
+
+        for (course_name, recommended_courses) in courses.recommendations.iter_all() {
+            let course_term = match Plan::find_term(courses, &terms, course_name) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            for recommended in recommended_courses {
+                preferences_count += 1;
+
+                let recommended_term = Plan::find_term(courses, &terms, recommended);
+                if recommended_term.is_some_and(|x| x < course_term) {
+                    continue;
+                }
+
+                violations.push(SoftViolation::RecommendationNotMet {
+                    course: course_name.clone(),
+                    term: course_term,
+                    recommended: recommended.clone(),
+                    recommended_term,
+                });
+            }
+        }
+
+        for (course_name, preferred_term) in &courses.preferred_terms {
+            let course_term = match terms.iter().position(|x| x.has_course(course_name)) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            preferences_count += 1;
+
+            let planned_term = &terms[course_term].term_type;
+            if planned_term == preferred_term {
+                continue;
+            }
+
+            let is_offered = courses
+                .master_list
+                .get(course_name)
+                .is_some_and(|x| x.is_available(preferred_term));
+
+            violations.push(SoftViolation::PreferredTermMissed {
+                course: course_name.clone(),
+                term: course_term,
+                preferred: preferred_term.clone(),
+                planned: planned_term.clone(),
+                is_offered,
+            });
+        }
+
+        violations.sort_by(|a, b| (a.term(), a.course()).cmp(&(b.term(), b.course())));
+
+        Plan {
+            terms,
+            violations,
+            preferences_count,
+            trace: None,
+        }
+    }
+
+    pub(crate) fn with_trace(mut self, trace: Option<ScheduleTrace>) -> Plan {
+        self.trace = trace;
+        self
+    }
+
+    /// Finds the first term taking the course or one of its equivalents.
+    fn find_term(courses: &Courses, terms: &[Term], course: &String) -> Option<usize> {
+        let equivalents = courses.get_equivalents_for(course).unwrap_or_default();
+
+        terms
+            .iter()
+            .position(|x| x.has_course(course) || equivalents.iter().any(|y| x.has_course(y)))
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    pub fn into_terms(self) -> Vec<Term> {
+        self.terms
+    }
+
+    pub fn violations(&self) -> &[SoftViolation] {
+        &self.violations
+    }
+
+    /// Gets the decisions the scheduler made about each course, when the
+    /// options it was planned with asked for them.
+    pub fn trace(&self) -> Option<&ScheduleTrace> {
+        self.trace.as_ref()
+    }
+
+    /// The share of soft preferences the plan follows, from 0 to 1. A plan
+    /// without any soft preferences scores 1.
+    pub fn score(&self) -> f64 {
+        if self.preferences_count == 0 {
+            return 1.0;
+        }
+
+        let followed_count = self.preferences_count - self.violations.len();
+
+        followed_count as f64 / self.preferences_count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+
+    #[test]
+    fn test_plan_follows_recommendation() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("CS 10"), 4);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 11"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+
+        courses.add_recommendation(&second_course_name, &first_course_name);
+
+        let result: Option<Plan> = courses.get_plan([8, 8, 8, 8]);
+        assert_ne!(result, None);
+
+        let plan: Plan = result.unwrap();
+        assert_eq!(plan.terms().len(), 2);
+        assert!(plan.terms()[0].has_course(&first_course_name));
+        assert!(plan.violations().is_empty());
+        assert_eq!(plan.score(), 1.0);
+    }
+
+    #[test]
+    fn test_plan_follows_preferred_term() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("CS 10"), 4);
+        let first_course_name = first_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.prefer_term(&first_course_name, &TermType::Spring);
+
+        let plan: Plan = courses.get_plan([8, 8, 8, 8]).unwrap();
+        assert_eq!(plan.terms().len(), 1);
+        assert_eq!(plan.terms()[0].term_type, TermType::Spring);
+        assert!(plan.violations().is_empty());
+    }
+
+    #[test]
+    fn test_plan_ignores_unmeetable_preferences() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("CS 10"), 4);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 20"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        let third_course: Course = Course::new(String::from("CS 21"), 4);
+        let third_course_name = third_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+        courses.add_course(third_course);
+
+        courses.add_recommendation(&first_course_name, "CS 9");
+        courses.add_recommendation(&second_course_name, &third_course_name);
+        courses.add_prerequisite(&third_course_name, &second_course_name);
+        courses.prefer_term(&third_course_name, &TermType::Summer);
+
+        let plan: Plan = courses.get_plan([8, 8, 8, 0]).unwrap();
+        assert_eq!(plan.terms().len(), 2);
+        assert_eq!(plan.terms()[0].term_type, TermType::Fall);
+        assert_eq!(plan.terms()[0].year(), 1);
+        assert!(plan.terms()[0].has_course(&first_course_name));
+        assert!(plan.terms()[0].has_course(&second_course_name));
+        assert_eq!(plan.terms()[1].term_type, TermType::Winter);
+        assert_eq!(plan.violations().len(), 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_plan() {
+        let mut courses: Courses = Courses::new();
+        let first_course: Course = Course::new(String::from("CS 10"), 4);
+        let first_course_name = first_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_recommendation(&first_course_name, "CS 9");
+
+        let plan: Plan = courses.get_plan([8, 8, 8, 8]).unwrap();
+        assert_eq!(plan.violations().len(), 1);
+
+        let serialized: String = serde_json::to_string(&plan).unwrap();
+        let deserialized: Plan = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized, plan);
+    }
+
+    #[test]
+    fn test_plan_lists_violations() {
+        let mut courses: Courses = Courses::new();
+        let mut first_course: Course = Course::new(String::from("CS 10"), 4);
+        first_course.available_by(&TermType::Fall);
+        let first_course_name = first_course.name.to_string();
+
+        let second_course: Course = Course::new(String::from("CS 11"), 4);
+        let second_course_name = second_course.name.to_string();
+
+        courses.add_course(first_course);
+        courses.add_course(second_course);
+
+        courses.prefer_term(&first_course_name, &TermType::Winter);
+        courses.add_recommendation(&second_course_name, "CS 9");
+
+        let plan: Plan = courses.get_plan([8, 8, 8, 8]).unwrap();
+        assert_eq!(plan.violations().len(), 2);
+        assert_eq!(plan.score(), 0.0);
+
+        for violation in plan.violations() {
+            println!("{}", violation);
+        }
+
+        assert!(plan
+            .violations()
+            .contains(&SoftViolation::PreferredTermMissed {
+                course: first_course_name,
+                term: 0,
+                preferred: TermType::Winter,
+                planned: TermType::Fall,
+                is_offered: false,
+            }));
+    }
+}
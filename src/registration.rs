@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+
+use crate::{Courses, Credits, TermType};
+
+/// Courses to register for together: a course on its own, or the courses of
+/// a concurrency group that are not completed yet with their combined units.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CourseBundle {
+    pub courses: Vec<String>,
+    pub units: Credits,
+}
+
+impl CourseBundle {
+    pub fn is_concurrent(&self) -> bool {
+        self.courses.len() > 1
+    }
+}
+
+impl Courses {
+    /// Checks whether the course is offered in the term and has every
+    /// prerequisite, prerequisite choice and earlier course of its sequence
+    /// completed.
+    fn is_eligible(
+        &self,
+        course_name: &String,
+        term: &TermType,
+        completed_courses: &HashSet<String>,
+    ) -> bool {
+        let course = match self.master_list.get(course_name) {
+            Some(x) => x,
+            None => return false,
+        };
+
+        let earlier_courses: Vec<String> = self
+            .get_sequence_for(course_name)
+            .map(|x| x.into_iter().take_while(|y| y != course_name).collect())
+            .unwrap_or_default();
+
+        course.is_available(term)
+            && self
+                .get_prerequisites(course_name)
+                .unwrap_or_default()
+                .iter()
+                .chain(&earlier_courses)
+                .all(|x| completed_courses.contains(x))
+            && self
+                .get_prerequisite_choices(course_name)
+                .unwrap_or_default()
+                .iter()
+                .all(|x| x.iter().any(|y| completed_courses.contains(y)))
+    }
+
+    /// Gets the courses that can be registered for in the coming term with
+    /// the courses completed so far, sorted by name. Concurrent courses come
+    /// as one bundle, which is only eligible when each of its courses is.
+    pub fn get_eligible_courses(
+        &self,
+        term: &TermType,
+        completed_courses: &HashSet<String>,
+    ) -> Vec<CourseBundle> {
+        let mut completed_courses: HashSet<String> = completed_courses.clone();
+        for course_name in completed_courses.clone() {
+            completed_courses.extend(self.get_equivalents_for(&course_name).unwrap_or_default());
+        }
+
+        let mut handled_courses: HashSet<String> = HashSet::new();
+        let mut bundles: Vec<CourseBundle> = Vec::new();
+
+        for course_name in self.get_course_names() {
+            if completed_courses.contains(&course_name) || handled_courses.contains(&course_name) {
+                continue;
+            }
+
+            let bundle_courses: HashSet<String> = match self.get_concurrents_for(&course_name) {
+                Some((concurrents, _)) => concurrents
+                    .into_iter()
+                    .filter(|x| !completed_courses.contains(x))
+                    .collect(),
+                None => HashSet::from([course_name.clone()]),
+            };
+            handled_courses.extend(bundle_courses.iter().cloned());
+
+            // Courses whose units are too large to count can not fit any term.
+            let units: Credits = match self.get_concurrents_units(&bundle_courses) {
+                Ok(x) => x,
+                Err(_) => continue,
+            };
+
+            if !bundle_courses
+                .iter()
+                .all(|x| self.is_eligible(x, term, &completed_courses))
+            {
+                continue;
+            }
+
+            let mut courses: Vec<String> = bundle_courses.iter().cloned().collect();
+            courses.sort();
+
+            bundles.push(CourseBundle { courses, units });
+        }
+
+        bundles.sort_by(|x, y| x.courses.cmp(&y.courses));
+        bundles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+
+    fn get_names(bundles: &[CourseBundle]) -> Vec<String> {
+        bundles.iter().map(|x| x.courses.join(" + ")).collect()
+    }
+
+    #[test]
+    fn test_get_eligible_courses() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+        courses.add_course(Course::new(String::from("CS 105"), 4));
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("CS 201L"), 2));
+        courses.add_course(Course::new(String::from("CS 301"), 4));
+        courses.add_course(Course::new(String::from("CS 490"), 4));
+        courses.add_course(Course::new(String::from("CS 491"), 4));
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_prerequisite_choice(
+            &String::from("CS 301"),
+            &[String::from("CS 201"), String::from("CS 105")],
+        );
+        courses.add_sequence(&[String::from("CS 490"), String::from("CS 491")]);
+        courses.add_equivalence("CS 105", "CS 101");
+
+        let bundles: Vec<CourseBundle> =
+            courses.get_eligible_courses(&TermType::Fall, &HashSet::new());
+        assert_eq!(get_names(&bundles), vec!["CS 101", "CS 105", "CS 490"]);
+
+        let bundles: Vec<CourseBundle> =
+            courses.get_eligible_courses(&TermType::Winter, &HashSet::new());
+        assert_eq!(get_names(&bundles), vec!["CS 105", "CS 490"]);
+
+        let completed_courses: HashSet<String> =
+            HashSet::from([String::from("CS 101"), String::from("CS 490")]);
+        let bundles: Vec<CourseBundle> =
+            courses.get_eligible_courses(&TermType::Winter, &completed_courses);
+        assert_eq!(
+            get_names(&bundles),
+            vec!["CS 201 + CS 201L", "CS 301", "CS 491"]
+        );
+        assert!(bundles[0].is_concurrent());
+        assert_eq!(bundles[0].units, Credits::from(6));
+    }
+
+    #[test]
+    fn test_get_eligible_courses_with_partial_group() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+        courses.add_course(Course::new(String::from("CS 105"), 4));
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("CS 201L"), 2));
+        courses.add_course(Course::new(String::from("CS 301"), 4));
+        courses.add_course(Course::new(String::from("CS 490"), 4));
+        courses.add_course(Course::new(String::from("CS 491"), 4));
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_prerequisite_choice(
+            &String::from("CS 301"),
+            &[String::from("CS 201"), String::from("CS 105")],
+        );
+        courses.add_sequence(&[String::from("CS 490"), String::from("CS 491")]);
+        courses.add_equivalence("CS 105", "CS 101");
+
+        let completed_courses: HashSet<String> =
+            HashSet::from([String::from("CS 101"), String::from("CS 201L")]);
+        let bundles: Vec<CourseBundle> =
+            courses.get_eligible_courses(&TermType::Spring, &completed_courses);
+
+        assert_eq!(get_names(&bundles), vec!["CS 201", "CS 301", "CS 490"]);
+        assert!(!bundles[0].is_concurrent());
+        assert_eq!(bundles[0].units, Credits::from(4));
+    }
+}
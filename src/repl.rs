@@ -0,0 +1,629 @@
+//! An interactive shell for building a catalog a course at a time and
+//! planning it again after every change.
+
+use std::borrow::Cow;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use cle_rust::{Course, Courses, Credits, ScheduleOptions, TermType};
+
+use crate::{explain_course, load_catalog, parse_unit_limits, plan_courses, plan_to_text};
+
+const COMMANDS: [&str; 16] = [
+    "add",
+    "remove",
+    "add-prereq",
+    "remove-prereq",
+    "add-concurrent",
+    "remove-concurrent",
+    "complete",
+    "limits",
+    "start",
+    "plan",
+    "show",
+    "save",
+    "load",
+    "help",
+    "quit",
+    "exit",
+];
+
+const HELP: &str = "Course names with spaces go in double quotes, such as \"CS 101\".
+
+  add <course> <credits> [term...]   add a course offered in the given terms,
+                                     or every term; credits may be a range
+  remove <course>                    remove a course
+  add-prereq <course> <prereq>...    require a prerequisite, or any one of
+                                     several when more than one is given
+  remove-prereq <course> <prereq>    remove a prerequisite
+  add-concurrent <course> <course>   require two courses in the same term
+  remove-concurrent <course> <course>
+  complete <course>                  mark a course as already completed
+  limits <units>[,<units>,...]       set the unit limit of every term, or of
+                                     Fall, Winter, Spring and Summer
+  start <term>                       plan from the given term
+  plan                               plan every course
+  show [course]                      list the courses, or explain one
+  save [path]                        save the catalog as JSON
+  load <path>                        load a catalog file
+  quit                               leave the shell
+";
+
+/// What the shell does after a command.
+#[derive(Debug, PartialEq)]
+pub enum Reply {
+    Print(String),
+    Quit,
+}
+
+/// The catalog and schedule options that commands act on.
+pub struct Session {
+    courses: Courses,
+    options: ScheduleOptions,
+    path: Option<PathBuf>,
+}
+
+/// Splits a command line into words, keeping text in double quotes
+/// together.
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut word: Option<String> = None;
+    let mut is_quoted = false;
+
+    for character in line.chars() {
+        match character {
+            '"' => {
+                is_quoted = !is_quoted;
+                word.get_or_insert_with(String::new);
+            }
+            _ if character.is_whitespace() && !is_quoted => {
+                words.extend(word.take());
+            }
+            _ => word.get_or_insert_with(String::new).push(character),
+        }
+    }
+
+    if is_quoted {
+        return Err(String::from("missing closing quote"));
+    }
+
+    words.extend(word.take());
+    Ok(words)
+}
+
+fn parse_credit_range(text: &str) -> Result<(Credits, Credits), String> {
+    let to_credits = |x: &str| x.parse::<Credits>().map_err(|e| e.to_string());
+
+    match text.split_once('-') {
+        Some((min_credits, max_credits)) => {
+            Ok((to_credits(min_credits)?, to_credits(max_credits)?))
+        }
+        None => {
+            let credits = to_credits(text)?;
+            Ok((credits, credits))
+        }
+    }
+}
+
+impl Session {
+    pub fn new(courses: Courses, options: ScheduleOptions, path: Option<PathBuf>) -> Session {
+        Session {
+            courses,
+            options,
+            path,
+        }
+    }
+
+    pub fn course_names(&self) -> Vec<String> {
+        self.courses.get_course_names()
+    }
+
+    fn known_course(&self, course: &str) -> Result<String, String> {
+        match self.courses.get_course(course) {
+            Some(x) => Ok(x.name.clone()),
+            None => Err(format!("unknown course '{}'", course)),
+        }
+    }
+
+    fn list_courses(&self) -> String {
+        if self.courses.is_empty() {
+            return String::from("No courses yet; add one with `add`\n");
+        }
+
+        let mut text = String::new();
+        for course_name in self.courses.get_course_names() {
+            let course: &Course = self.courses.get_course(&course_name).unwrap();
+
+            let mut prerequisites: Vec<String> = self
+                .courses
+                .get_prerequisites(&course_name)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            prerequisites.sort();
+            prerequisites.extend(
+                self.courses
+                    .get_prerequisite_choices(&course_name)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|x| format!("({})", x.join(" or "))),
+            );
+
+            text.push_str(&format!("{} ({} units)", course_name, course.credits));
+            if !prerequisites.is_empty() {
+                text.push_str(&format!(" requires {}", prerequisites.join(", ")));
+            }
+            if self.options.is_completed(&course_name) {
+                text.push_str(", completed");
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Runs one command line, getting the text to print or whether to quit.
+    pub fn execute(&mut self, line: &str) -> Result<Reply, String> {
+        let words: Vec<String> = split_words(line)?;
+        let (command, arguments) = match words.split_first() {
+            Some((command, arguments)) => (command.to_lowercase(), arguments),
+            None => return Ok(Reply::Print(String::new())),
+        };
+
+        let print = |text: String| Ok(Reply::Print(text));
+
+        match (command.as_str(), arguments) {
+            ("add", [course_name, credits, terms @ ..]) => {
+                if self.courses.get_course(course_name).is_some() {
+                    return Err(format!("{} is already in the catalog", course_name));
+                }
+
+                let (min_credits, max_credits) = parse_credit_range(credits)?;
+                let mut course = Course::new(course_name.clone(), min_credits);
+                course.variable_credits(max_credits);
+
+                for term in terms {
+                    course.available_by(&term.parse::<TermType>().map_err(|e| e.to_string())?);
+                }
+
+                self.courses.add_course(course);
+                print(format!("Added {}\n", course_name))
+            }
+            ("remove", [course_name]) => {
+                let course_name = self.known_course(course_name)?;
+                self.courses.remove_course(&course_name);
+                print(format!("Removed {}\n", course_name))
+            }
+            ("add-prereq", [course_name, prerequisites @ ..]) if !prerequisites.is_empty() => {
+                let course_name = self.known_course(course_name)?;
+                let prerequisites: Vec<String> = prerequisites
+                    .iter()
+                    .map(|x| self.known_course(x))
+                    .collect::<Result<_, _>>()?;
+
+                self.courses
+                    .add_prerequisite_choice(&course_name, &prerequisites);
+                print(format!(
+                    "{} now requires {}\n",
+                    course_name,
+                    prerequisites.join(" or ")
+                ))
+            }
+            ("remove-prereq", [course_name, prerequisite]) => {
+                let course_name = self.known_course(course_name)?;
+                let mut is_removed = self
+                    .courses
+                    .remove_prerequisite(&course_name, prerequisite)
+                    .is_some();
+
+                // A choice is shared by the courses taken together, so the
+                // remaining options replace it for each of them.
+                let mut group: Vec<String> = match self.courses.get_concurrents_for(&course_name) {
+                    Some((x, _)) => x.into_iter().collect(),
+                    None => Vec::new(),
+                };
+                group.push(course_name.clone());
+
+                let choices: Vec<Vec<String>> = self
+                    .courses
+                    .get_prerequisite_choices(&course_name)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|x| x.contains(prerequisite))
+                    .collect();
+                for choice in choices {
+                    for group_course in &group {
+                        self.courses
+                            .remove_prerequisite_choice(group_course, &choice);
+                    }
+
+                    let options: Vec<String> =
+                        choice.into_iter().filter(|x| x != prerequisite).collect();
+                    self.courses.add_prerequisite_choice(&course_name, &options);
+                    is_removed = true;
+                }
+
+                match is_removed {
+                    true => print(format!(
+                        "{} no longer requires {}\n",
+                        course_name, prerequisite
+                    )),
+                    false => Err(format!("{} does not require {}", course_name, prerequisite)),
+                }
+            }
+            ("add-concurrent", [course_name, concurrent]) => {
+                let course_name = self.known_course(course_name)?;
+                let concurrent = self.known_course(concurrent)?;
+
+                match self.courses.add_concurrency(&course_name, &concurrent) {
+                    Ok(()) => print(format!(
+                        "{} and {} are now taken together\n",
+                        course_name, concurrent
+                    )),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            ("remove-concurrent", [course_name, concurrent]) => {
+                match self.courses.remove_concurrency(course_name, concurrent) {
+                    Some(_) => print(format!(
+                        "{} and {} are no longer taken together\n",
+                        course_name, concurrent
+                    )),
+                    None => Err(format!(
+                        "{} and {} are not taken together",
+                        course_name, concurrent
+                    )),
+                }
+            }
+            ("complete", [course_name]) => {
+                let course_name = self.known_course(course_name)?;
+                self.options.complete(&course_name);
+                print(format!("Marked {} as completed\n", course_name))
+            }
+            ("limits", [limits]) => {
+                let limits: [Credits; 4] = parse_unit_limits(limits)?;
+                self.options.set_unit_limits(limits);
+                print(String::from("Updated the unit limits\n"))
+            }
+            ("start", [term]) => {
+                let term: TermType = term
+                    .parse()
+                    .map_err(|e: cle_rust::ParseTermTypeError| e.to_string())?;
+                self.options.start_at(&term);
+                print(format!("Planning from {}\n", term))
+            }
+            ("plan", []) => print(plan_to_text(&plan_courses(&self.courses, &self.options)?)),
+            ("show", []) => print(self.list_courses()),
+            ("show", [course_name]) => {
+                print(explain_course(&self.courses, course_name, &self.options)?)
+            }
+            ("save", [path]) => self.save(PathBuf::from(path)),
+            ("save", []) => match self.path.clone() {
+                Some(path) if path.extension().is_some_and(|x| x == "json") => self.save(path),
+                _ => Err(String::from(
+                    "give a path to save to; catalogs are saved as JSON",
+                )),
+            },
+            ("load", [path]) => {
+                let path = PathBuf::from(path);
+                let catalog = load_catalog(&path)?;
+
+                self.courses = catalog.courses;
+                self.path = Some(path.clone());
+
+                let mut text: String = catalog
+                    .warnings
+                    .iter()
+                    .map(|x| format!("warning: {}\n", x))
+                    .collect();
+                text.push_str(&format!(
+                    "Loaded {} courses from {}\n",
+                    self.courses.len(),
+                    path.display()
+                ));
+                print(text)
+            }
+            ("help", []) => print(HELP.to_string()),
+            ("quit", []) | ("exit", []) => Ok(Reply::Quit),
+            _ if COMMANDS.contains(&command.as_str()) => {
+                Err(format!("wrong arguments for `{}`; see `help`", command))
+            }
+            _ => Err(format!("unknown command `{}`; see `help`", command)),
+        }
+    }
+
+    fn save(&mut self, path: PathBuf) -> Result<Reply, String> {
+        let text: String = serde_json::to_string_pretty(&self.courses)
+            .map_err(|e| format!("could not save the catalog: {}", e))?;
+        fs::write(&path, text).map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+
+        let reply = Reply::Print(format!(
+            "Saved {} courses to {}\n",
+            self.courses.len(),
+            path.display()
+        ));
+        self.path = Some(path);
+
+        Ok(reply)
+    }
+}
+
+/// Finds where the word being typed starts and the completions for it:
+/// a command for the first word and a course name after that.
+fn complete_word(line: &str, course_names: &[String]) -> (usize, Vec<Pair>) {
+    let is_quoted = line.matches('"').count() % 2 == 1;
+    let start: usize = match is_quoted {
+        true => line.rfind('"').unwrap(),
+        false => line
+            .rfind(char::is_whitespace)
+            .map_or(0, |x| x + line[x..].chars().next().unwrap().len_utf8()),
+    };
+
+    let prefix: String = line[start..].trim_start_matches('"').to_lowercase();
+
+    if line[..start].trim().is_empty() {
+        let commands: Vec<Pair> = COMMANDS
+            .iter()
+            .filter(|x| x.starts_with(&prefix))
+            .map(|x| Pair {
+                display: x.to_string(),
+                replacement: format!("{} ", x),
+            })
+            .collect();
+
+        return (start, commands);
+    }
+
+    let courses: Vec<Pair> = course_names
+        .iter()
+        .filter(|x| x.to_lowercase().starts_with(&prefix))
+        .map(|x| Pair {
+            display: x.clone(),
+            replacement: match x.contains(char::is_whitespace) {
+                true => format!("\"{}\" ", x),
+                false => format!("{} ", x),
+            },
+        })
+        .collect();
+
+    (start, courses)
+}
+
+/// Completes commands and the course names of the current catalog.
+struct ShellHelper {
+    course_names: Vec<String>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        position: usize,
+        _context: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        Ok(complete_word(&line[..position], &self.course_names))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {
+    fn highlight_prompt<'b, 's: 'b, 'p: 'b>(
+        &'s self,
+        prompt: &'p str,
+        _default: bool,
+    ) -> Cow<'b, str> {
+        Cow::Borrowed(prompt)
+    }
+}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|x| PathBuf::from(x).join(".cle-rust-history"))
+}
+
+/// Reads commands until the user quits, keeping the history between runs.
+pub fn run(mut session: Session) -> Result<ExitCode, String> {
+    let mut editor: Editor<ShellHelper, DefaultHistory> =
+        Editor::new().map_err(|e| format!("could not start the shell: {}", e))?;
+    editor.set_helper(Some(ShellHelper {
+        course_names: session.course_names(),
+    }));
+
+    let history_path: Option<PathBuf> = history_path();
+    if let Some(path) = &history_path {
+        // There is no history yet the first time the shell runs.
+        let _ = editor.load_history(path);
+    }
+
+    println!("Type `help` to list the commands.");
+
+    loop {
+        let line: String = match editor.readline("cle> ") {
+            Ok(x) => x,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(format!("could not read the command: {}", e)),
+        };
+
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+
+        match session.execute(&line) {
+            Ok(Reply::Print(text)) => print!("{}", text),
+            Ok(Reply::Quit) => break,
+            Err(message) => eprintln!("error: {}", message),
+        }
+
+        if let Some(helper) = editor.helper_mut() {
+            helper.course_names = session.course_names();
+        }
+    }
+
+    if let Some(path) = &history_path {
+        let _ = editor.save_history(path);
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn print_of(session: &mut Session, line: &str) -> String {
+        match session.execute(line) {
+            Ok(Reply::Print(text)) => text,
+            x => panic!("unexpected reply to '{}': {:?}", line, x),
+        }
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words("add \"CS 101\" 4 fall"),
+            Ok(vec![
+                String::from("add"),
+                String::from("CS 101"),
+                String::from("4"),
+                String::from("fall")
+            ])
+        );
+        assert_eq!(split_words("  "), Ok(Vec::new()));
+        assert!(split_words("add \"CS 101").is_err());
+    }
+
+    #[test]
+    fn test_session_commands() {
+        let mut session = Session::new(Courses::new(), ScheduleOptions::new([8, 8, 8, 8]), None);
+
+        print_of(&mut session, "add \"CS 101\" 4 fall winter");
+        print_of(&mut session, "add \"CS 105\" 4");
+        print_of(&mut session, "add \"CS 201\" 4");
+        print_of(&mut session, "add \"CS 201L\" 1-2");
+        print_of(&mut session, "add-prereq \"CS 201\" \"CS 101\" \"CS 105\"");
+        print_of(&mut session, "add-concurrent \"CS 201\" \"CS 201L\"");
+        assert!(session.execute("add \"CS 101\" 4").is_err());
+        assert!(session.execute("add-prereq \"CS 201\" \"CS 100\"").is_err());
+
+        assert_eq!(
+            print_of(&mut session, "show"),
+            "CS 101 (4 units)\nCS 105 (4 units)\nCS 201 (4 units) requires (CS 101 or CS 105)\nCS 201L (1 units) requires (CS 101 or CS 105)\n"
+        );
+
+        let plan: String = print_of(&mut session, "plan");
+        assert!(plan.starts_with("Term 1 (year 1) - Fall: 8 units total\n"));
+        assert!(plan.contains("Winter: 5 units total\n"));
+
+        print_of(&mut session, "remove-prereq \"CS 201\" \"CS 105\"");
+        assert_eq!(
+            print_of(&mut session, "show"),
+            "CS 101 (4 units)\nCS 105 (4 units)\nCS 201 (4 units) requires CS 101\nCS 201L (1 units) requires CS 101\n"
+        );
+        print_of(&mut session, "complete \"CS 101\"");
+        print_of(&mut session, "start spring");
+
+        let plan: String = print_of(&mut session, "plan");
+        assert!(plan.starts_with("Term 1 (year 1) - Spring: "));
+        assert!(!plan.contains("CS 101"));
+
+        assert_eq!(session.execute("quit"), Ok(Reply::Quit));
+        assert!(session.execute("remove").is_err());
+        assert!(session.execute("frobnicate").is_err());
+    }
+
+    #[test]
+    fn test_session_remove_concurrent_course() {
+        let mut session = Session::new(Courses::new(), ScheduleOptions::new([8, 8, 8, 8]), None);
+
+        print_of(&mut session, "add \"CS 201\" 4");
+        print_of(&mut session, "add \"CS 201L\" 1");
+        print_of(&mut session, "add-concurrent \"CS 201\" \"CS 201L\"");
+        print_of(&mut session, "remove \"CS 201L\"");
+
+        assert_eq!(
+            print_of(&mut session, "plan"),
+            "Term 1 (year 1) - Fall: 4 units total\nCS 201: 4 units\n\n"
+        );
+    }
+
+    #[test]
+    fn test_session_limits_keep_options() {
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options
+            .start_at(&TermType::Winter)
+            .complete("CS 101")
+            .pin("CS 201", &TermType::Spring, 2);
+
+        let mut session = Session::new(Courses::new(), options, None);
+        print_of(&mut session, "limits 12,12,16,0");
+
+        let mut expected_options = ScheduleOptions::new([12, 12, 16, 0]);
+        expected_options
+            .start_at(&TermType::Winter)
+            .complete("CS 101")
+            .pin("CS 201", &TermType::Spring, 2);
+        assert_eq!(session.options, expected_options);
+    }
+
+    #[test]
+    fn test_session_save_and_load() {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("cle-rust-shell-{}.json", std::process::id()));
+        let path_text: String = path.display().to_string();
+
+        let mut session = Session::new(Courses::new(), ScheduleOptions::new([8, 8, 8, 8]), None);
+        print_of(&mut session, "add \"CS 101\" 4");
+        print_of(&mut session, "add \"CS 201\" 4");
+        print_of(&mut session, "add-prereq \"CS 201\" \"CS 101\"");
+        print_of(&mut session, &format!("save \"{}\"", path_text));
+
+        let mut loaded_session =
+            Session::new(Courses::new(), ScheduleOptions::new([8, 8, 8, 8]), None);
+        print_of(&mut loaded_session, &format!("load \"{}\"", path_text));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded_session.courses, session.courses);
+    }
+
+    #[test]
+    fn test_complete_word() {
+        let course_names: Vec<String> = vec![
+            String::from("CS 101"),
+            String::from("CS 201"),
+            String::from("MATH20"),
+        ];
+        let replacements = |line: &str| -> (usize, Vec<String>) {
+            let (start, pairs) = complete_word(line, &course_names);
+            (start, pairs.into_iter().map(|x| x.replacement).collect())
+        };
+
+        assert_eq!(
+            replacements("add-c"),
+            (0, vec![String::from("add-concurrent ")])
+        );
+        assert_eq!(
+            replacements("show \"cs 2"),
+            (5, vec![String::from("\"CS 201\" ")])
+        );
+        assert_eq!(replacements("show ma"), (5, vec![String::from("MATH20 ")]));
+        assert_eq!(replacements("show c").1.len(), 2);
+    }
+}
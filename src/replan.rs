@@ -0,0 +1,393 @@
+use std::collections::HashSet;
+
+use crate::validation::{self, Violation};
+use crate::{Courses, ScheduleOptions, Term};
+
+/// How many more terms are tried past the end of a plan when placing a
+/// course that does not fit into any of its terms.
+const EXTRA_TERMS_COUNT: usize = 4;
+
+/// What happened in the terms of a plan that are over: how many of its terms
+/// are done and which of their courses were failed or dropped, which have to
+/// be taken again.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Progress {
+    terms_done: usize,
+    failed_courses: HashSet<String>,
+}
+
+impl Progress {
+    pub fn new(terms_done: usize) -> Progress {
+        Progress {
+            terms_done,
+            failed_courses: HashSet::new(),
+        }
+    }
+
+    /// Marks a course of a term that is done as failed or dropped, so it
+    /// does not count as completed.
+    pub fn fail(&mut self, course: &str) -> &mut Progress {
+        self.failed_courses.insert(course.to_string());
+        self
+    }
+
+    pub fn terms_done(&self) -> usize {
+        self.terms_done
+    }
+
+    pub fn is_failed(&self, course: &str) -> bool {
+        self.failed_courses.contains(course)
+    }
+}
+
+/// Removes every instance of the courses from the terms.
+fn remove_courses(terms: &mut [Term], course_names: &[String]) {
+    for term in terms {
+        for course_name in course_names {
+            while term.remove_course(course_name).is_some() {}
+        }
+    }
+}
+
+impl Courses {
+    /// Gets the courses moved together with the course when re-planning:
+    /// what is left of its sequence in order, or of its concurrency group.
+    fn get_replan_group(
+        &self,
+        course: &String,
+        completed_courses: &HashSet<String>,
+    ) -> Vec<String> {
+        let mut group: Vec<String> = match self.get_sequence_for(course) {
+            Some(sequence) => sequence,
+            None => {
+                let mut concurrents: Vec<String> = self
+                    .get_concurrents_for(course)
+                    .map(|x| x.0.into_iter().collect())
+                    .unwrap_or_else(|| vec![course.clone()]);
+                concurrents.sort();
+                concurrents
+            }
+        };
+        group.retain(|x| !completed_courses.contains(x));
+
+        group
+    }
+
+    /// Gets the violations of the terms after those done that re-planning
+    /// has to fix. Courses done before can not be moved any more, so a
+    /// sequence or concurrency group broken by them is let be.
+    fn get_replan_violations(
+        &self,
+        terms: &[Term],
+        terms_done: usize,
+        completed_courses: &HashSet<String>,
+        options: &ScheduleOptions,
+    ) -> Vec<Violation> {
+        validation::validate(self, terms, options)
+            .into_iter()
+            .filter(|x| x.term().is_some_and(|y| y >= terms_done))
+            .filter(|x| match x {
+                Violation::ConcurrentsSplit { missing, .. } => {
+                    !missing.iter().all(|y| completed_courses.contains(y))
+                }
+                Violation::SequenceBroken { previous_term, .. } => *previous_term >= terms_done,
+                _ => true,
+            })
+            .collect()
+    }
+
+    fn push_next_term(&self, terms: &mut Vec<Term>, options: &ScheduleOptions) {
+        let (term_type, year) = match terms.last() {
+            Some(last_term) => {
+                let term_type = self.get_next_term_for(last_term.term_type.clone());
+                let year = self.get_next_year_for(&term_type, last_term.year);
+
+                (term_type, year)
+            }
+            None => (options.start_term.clone(), 1),
+        };
+
+        let mut term = Term::new(&term_type, options.unit_limit(&term_type));
+        term.set_year(year);
+        terms.push(term);
+    }
+
+    /// Places the group into the earliest term after those done where it
+    /// breaks no rule, adding terms to the end of the plan as needed. A
+    /// sequence takes consecutive terms. False is given when no term fits.
+    fn place_replan_group(
+        &self,
+        terms: &mut Vec<Term>,
+        group: &[String],
+        terms_done: usize,
+        completed_courses: &HashSet<String>,
+        options: &ScheduleOptions,
+    ) -> bool {
+        let is_sequence: bool = self.get_sequence_for(&group[0]).is_some();
+        let terms_needed: usize = if is_sequence { group.len() } else { 1 };
+
+        'terms: for index in terms_done..terms.len() + EXTRA_TERMS_COUNT {
+            let mut candidate_terms: Vec<Term> = terms.clone();
+            while candidate_terms.len() < index + terms_needed {
+                self.push_next_term(&mut candidate_terms, options);
+            }
+
+            for (position, course_name) in group.iter().enumerate() {
+                // A course can be concurrent with one missing from the
+                // catalog, which has no units to place.
+                let course = match self.master_list.get(course_name) {
+                    Some(x) => x,
+                    None => continue,
+                };
+                let units = course
+                    .target_credits
+                    .clamp(course.credits, course.max_credits);
+                let term_index: usize = if is_sequence { index + position } else { index };
+
+                if candidate_terms[term_index].add_units(course, units) != Ok(true) {
+                    continue 'terms;
+                }
+            }
+
+            if self
+                .get_replan_violations(&candidate_terms, terms_done, completed_courses, options)
+                .is_empty()
+            {
+                *terms = candidate_terms;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Re-plans the terms after those done, once it is known which of their
+    /// courses were failed or dropped. Courses planned later that still
+    /// follow every rule stay in their terms, and only the courses that no
+    /// longer do are moved, along with the courses to take again, to the
+    /// earliest terms they fit in.
+    ///
+    /// The terms done are kept without their failed courses, so the new plan
+    /// can be validated like any other. None is given when some course can
+    /// not be placed in any term.
+    pub fn replan(
+        &self,
+        terms: &[Term],
+        progress: &Progress,
+        options: &ScheduleOptions,
+    ) -> Option<Vec<Term>> {
+        let terms_done: usize = progress.terms_done.min(terms.len());
+        let mut new_terms: Vec<Term> = terms.to_vec();
+
+        let failed_courses: Vec<String> = progress.failed_courses.iter().cloned().collect();
+        remove_courses(&mut new_terms[..terms_done], &failed_courses);
+
+        let mut completed_courses: HashSet<String> = options.completed_courses.clone();
+        completed_courses.extend(
+            new_terms[..terms_done]
+                .iter()
+                .flat_map(|x| &x.courses)
+                .map(|x| x.0.clone()),
+        );
+        for course_name in completed_courses.clone() {
+            completed_courses.extend(self.get_equivalents_for(&course_name).unwrap_or_default());
+        }
+
+        loop {
+            let violations: Vec<Violation> =
+                self.get_replan_violations(&new_terms, terms_done, &completed_courses, options);
+            if violations.is_empty() {
+                break;
+            }
+
+            for violation in violations {
+                let course_name: Option<String> = match violation {
+                    Violation::UnitLimitExceeded { term, .. } => {
+                        new_terms[term].courses.last().map(|x| x.0.clone())
+                    }
+                    Violation::ConcurrentsSplit { courses, .. } => courses.into_iter().next(),
+                    Violation::UnknownCourse { course, .. }
+                    | Violation::PrerequisiteNotMet { course, .. }
+                    | Violation::PrerequisiteChoiceNotMet { course, .. }
+                    | Violation::SequenceBroken { course, .. }
+                    | Violation::NotOffered { course, .. }
+                    | Violation::CreditsOutOfRange { course, .. } => Some(course),
+                    _ => None,
+                };
+
+                if let Some(course_name) = course_name {
+                    remove_courses(
+                        &mut new_terms[terms_done..],
+                        &self.get_replan_group(&course_name, &HashSet::new()),
+                    );
+                }
+            }
+        }
+
+        let mut handled_courses: HashSet<String> = new_terms
+            .iter()
+            .flat_map(|x| &x.courses)
+            .map(|x| x.0.clone())
+            .collect();
+        handled_courses.extend(completed_courses.iter().cloned());
+
+        let mut pending_groups: Vec<Vec<String>> = Vec::new();
+        for course_name in self.get_course_names() {
+            if handled_courses.contains(&course_name) {
+                continue;
+            }
+
+            let group: Vec<String> = self.get_replan_group(&course_name, &completed_courses);
+            remove_courses(&mut new_terms[terms_done..], &group);
+            handled_courses.extend(group.iter().cloned());
+            pending_groups.push(group);
+        }
+        pending_groups.sort_by_key(|x| {
+            self.get_longest_prerequisite_chain(&x[0])
+                .map_or(0, |y| y.len())
+        });
+
+        while !pending_groups.is_empty() {
+            let pending_groups_count: usize = pending_groups.len();
+
+            pending_groups.retain(|x| {
+                !self.place_replan_group(&mut new_terms, x, terms_done, &completed_courses, options)
+            });
+
+            if pending_groups.len() == pending_groups_count {
+                return None;
+            }
+        }
+
+        let mut index: usize = 0;
+        new_terms.retain(|x| {
+            index += 1;
+            index <= terms_done || !x.is_empty()
+        });
+
+        Some(new_terms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Course, TermType};
+
+    fn get_term(term_type: &TermType, year: u16, course_names: &[&str], courses: &Courses) -> Term {
+        let mut term = Term::new(term_type, 8);
+        term.set_year(year);
+
+        for course_name in course_names {
+            term.add(courses.get_course(course_name).unwrap()).unwrap();
+        }
+
+        term
+    }
+
+    fn get_names(terms: &[Term]) -> Vec<Vec<String>> {
+        terms
+            .iter()
+            .map(|x| x.courses().iter().map(|y| y.0.clone()).collect())
+            .collect()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_progress() {
+        let mut progress = Progress::new(2);
+        progress.fail("CS 101");
+
+        let text: String = serde_json::to_string(&progress).unwrap();
+        assert_eq!(serde_json::from_str::<Progress>(&text).unwrap(), progress);
+    }
+
+    #[test]
+    fn test_replan_failed_course() {
+        let mut courses: Courses = Courses::new();
+        for course_name in ["CS 101", "CS 201", "CS 301", "ART 1", "ART 2"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses.add_prerequisite(&String::from("CS 301"), &String::from("CS 201"));
+
+        let terms: Vec<Term> = vec![
+            get_term(&TermType::Fall, 1, &["CS 101", "ART 1"], &courses),
+            get_term(&TermType::Winter, 1, &["CS 201", "ART 2"], &courses),
+            get_term(&TermType::Spring, 1, &["CS 301"], &courses),
+        ];
+        let options = ScheduleOptions::new([8, 8, 8, 8]);
+
+        let mut progress = Progress::new(1);
+        progress.fail("CS 101");
+        let new_terms: Vec<Term> = courses.replan(&terms, &progress, &options).unwrap();
+
+        assert_eq!(
+            get_names(&new_terms),
+            vec![
+                vec!["ART 1"],
+                vec!["ART 2", "CS 101"],
+                vec!["CS 201"],
+                vec!["CS 301"]
+            ]
+        );
+        assert_eq!(new_terms[3].term_type(), &TermType::Summer);
+        assert!(courses.validate_terms_with(&new_terms, &options).is_empty());
+
+        let unchanged_terms: Vec<Term> =
+            courses.replan(&terms, &Progress::new(1), &options).unwrap();
+        assert_eq!(unchanged_terms, terms);
+    }
+
+    #[test]
+    fn test_replan_with_concurrents_and_sequences() {
+        let mut courses: Courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("CS 201L"), 2));
+        courses.add_course(Course::new(String::from("CS 490"), 2));
+        courses.add_course(Course::new(String::from("CS 491"), 2));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_sequence(&[String::from("CS 490"), String::from("CS 491")]);
+
+        let terms: Vec<Term> = vec![
+            get_term(
+                &TermType::Fall,
+                1,
+                &["CS 201", "CS 201L", "CS 490"],
+                &courses,
+            ),
+            get_term(&TermType::Winter, 1, &["CS 491"], &courses),
+        ];
+        let options = ScheduleOptions::new([8, 8, 8, 8]);
+
+        let mut progress = Progress::new(1);
+        progress.fail("CS 201").fail("CS 490");
+        let new_terms: Vec<Term> = courses.replan(&terms, &progress, &options).unwrap();
+
+        assert_eq!(
+            get_names(&new_terms),
+            vec![vec!["CS 201L"], vec!["CS 201", "CS 490"], vec!["CS 491"]]
+        );
+    }
+
+    #[test]
+    fn test_replan_with_dangling_concurrent() {
+        let mut courses: Courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+
+        let terms: Vec<Term> = vec![get_term(&TermType::Fall, 1, &["CS 201"], &courses)];
+        let options = ScheduleOptions::new([8, 8, 8, 8]);
+
+        let mut progress = Progress::new(1);
+        progress.fail("CS 201");
+        let new_terms = courses.replan(&terms, &progress, &options);
+
+        assert_eq!(new_terms.map(|x| get_names(&x)), None);
+    }
+}
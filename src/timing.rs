@@ -0,0 +1,331 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Courses, ScheduleOptions, Term, TermType, TracedTerm};
+
+/// When a course can be taken at the earliest and when a plan takes it.
+/// Slack is the number of terms between the two, so a course without slack
+/// delays every course after it whenever it slips.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CourseSlack {
+    pub course: String,
+    pub earliest: Option<TracedTerm>,
+    pub planned: Option<TracedTerm>,
+    pub slack: Option<usize>,
+}
+
+impl CourseSlack {
+    pub fn is_critical(&self) -> bool {
+        self.slack == Some(0)
+    }
+}
+
+/// Counts the terms from the start term to the given term of the plan.
+pub(crate) fn get_term_offset(start_term: &TermType, term: &TermType, year: u16) -> usize {
+    let start_index = start_term.clone() as usize;
+    let term_index = term.clone() as usize;
+
+    ((year.max(1) as usize - 1) * 4 + term_index).saturating_sub(start_index)
+}
+
+impl Courses {
+    /// Gets the term of the plan that comes the given number of terms after
+    /// the start term.
+    pub(crate) fn get_term_at(&self, start_term: &TermType, offset: usize) -> TracedTerm {
+        let mut term: TermType = start_term.clone();
+        let mut year: u16 = 1;
+
+        for _ in 0..offset {
+            term = self.get_next_term_for(term);
+            year = self.get_next_year_for(&term, year);
+        }
+
+        TracedTerm { term, year }
+    }
+
+    /// Checks whether the course can be taken in the term, counting courses
+    /// finished by then and the courses of its sequence taken before it.
+    fn can_take_in(
+        &self,
+        course_name: &String,
+        term: &TermType,
+        offset: usize,
+        finished_by: &HashMap<String, usize>,
+        options: &ScheduleOptions,
+        taken_before: &[String],
+    ) -> bool {
+        let course = match self.master_list.get(course_name) {
+            Some(x) => x,
+            None => return false,
+        };
+
+        let is_finished = |x: &String| {
+            finished_by.get(x).is_some_and(|y| *y <= offset) || taken_before.contains(x)
+        };
+
+        course.is_available(term)
+            && course.credits <= options.unit_limit(term)
+            && self
+                .get_prerequisites(course_name)
+                .unwrap_or_default()
+                .iter()
+                .all(is_finished)
+            && self
+                .get_prerequisite_choices(course_name)
+                .unwrap_or_default()
+                .iter()
+                .all(|x| x.iter().any(is_finished))
+    }
+
+    /// Finds the earliest term each course could be taken in, if every term
+    /// had room for every course. Courses still follow their prerequisites,
+    /// availability, concurrency groups and sequences, and a course that
+    /// could never be taken is left out.
+    pub fn get_earliest_terms(&self, options: &ScheduleOptions) -> HashMap<String, TracedTerm> {
+        let mut earliest_terms: HashMap<String, TracedTerm> = HashMap::new();
+        // The first term offset in which each course counts as taken.
+        let mut finished_by: HashMap<String, usize> = HashMap::new();
+
+        let finish = |finished_by: &mut HashMap<String, usize>, course: &String, offset| {
+            finished_by.entry(course.clone()).or_insert(offset);
+
+            for equivalent in self.get_equivalents_for(course).unwrap_or_default() {
+                finished_by.entry(equivalent).or_insert(offset);
+            }
+        };
+
+        for course_name in &options.completed_courses {
+            finish(&mut finished_by, course_name, 0);
+        }
+
+        let mut pending_courses: Vec<String> = self
+            .get_course_names()
+            .into_iter()
+            .filter(|x| !finished_by.contains_key(x))
+            .collect();
+
+        let mut term: TermType = options.start_term.clone();
+        let mut year: u16 = 1;
+        let mut offset: usize = 0;
+        let mut idle_terms_count = 0;
+
+        while !pending_courses.is_empty() && idle_terms_count < 4 {
+            let mut taken_courses: Vec<(String, TermType, u16, usize)> = Vec::new();
+            let mut handled_courses: HashSet<String> = HashSet::new();
+
+            for course_name in &pending_courses {
+                if handled_courses.contains(course_name) {
+                    continue;
+                }
+
+                if let Some(sequence) = self.get_sequence_for(course_name) {
+                    handled_courses.extend(sequence.iter().cloned());
+
+                    let mut sequence_term: TermType = term.clone();
+                    let mut sequence_year: u16 = year;
+                    let mut sequence_terms: Vec<(TermType, u16)> = Vec::new();
+
+                    for (index, sequence_course) in sequence.iter().enumerate() {
+                        if !self.can_take_in(
+                            sequence_course,
+                            &sequence_term,
+                            offset + index,
+                            &finished_by,
+                            options,
+                            &sequence[..index],
+                        ) {
+                            break;
+                        }
+
+                        sequence_terms.push((sequence_term.clone(), sequence_year));
+                        sequence_term = self.get_next_term_for(sequence_term);
+                        sequence_year = self.get_next_year_for(&sequence_term, sequence_year);
+                    }
+
+                    if sequence_terms.len() == sequence.len() {
+                        for (index, (sequence_course, (x, y))) in
+                            sequence.iter().zip(sequence_terms).enumerate()
+                        {
+                            taken_courses.push((sequence_course.clone(), x, y, offset + index));
+                        }
+                    }
+                } else if let Some((concurrents, units)) = self.get_concurrents_for(course_name) {
+                    handled_courses.extend(concurrents.iter().cloned());
+
+                    if units.is_ok_and(|x| x <= options.unit_limit(&term))
+                        && concurrents.iter().all(|x| {
+                            finished_by.contains_key(x)
+                                || self.can_take_in(x, &term, offset, &finished_by, options, &[])
+                        })
+                    {
+                        for concurrent in concurrents {
+                            taken_courses.push((concurrent, term.clone(), year, offset));
+                        }
+                    }
+                } else if self.can_take_in(course_name, &term, offset, &finished_by, options, &[]) {
+                    taken_courses.push((course_name.clone(), term.clone(), year, offset));
+                }
+            }
+
+            match taken_courses.is_empty() {
+                true => idle_terms_count += 1,
+                false => idle_terms_count = 0,
+            }
+
+            for (course_name, taken_term, taken_year, taken_offset) in taken_courses {
+                if earliest_terms.contains_key(&course_name)
+                    || finished_by.contains_key(&course_name)
+                {
+                    continue;
+                }
+
+                finish(&mut finished_by, &course_name, taken_offset + 1);
+                earliest_terms.insert(
+                    course_name,
+                    TracedTerm {
+                        term: taken_term,
+                        year: taken_year,
+                    },
+                );
+            }
+            pending_courses.retain(|x| !finished_by.contains_key(x));
+
+            term = self.get_next_term_for(term);
+            year = self.get_next_year_for(&term, year);
+            offset += 1;
+        }
+
+        earliest_terms
+    }
+
+    /// Compares the earliest term of each course that is not completed with
+    /// the term the plan takes it in, with the courses that have the least
+    /// slack first.
+    pub fn get_slack(&self, terms: &[Term], options: &ScheduleOptions) -> Vec<CourseSlack> {
+        let earliest_terms: HashMap<String, TracedTerm> = self.get_earliest_terms(options);
+
+        let mut course_slacks: Vec<CourseSlack> = self
+            .get_course_names()
+            .into_iter()
+            .filter(|x| !options.is_completed(x))
+            .map(|course_name| {
+                let earliest: Option<TracedTerm> = earliest_terms.get(&course_name).cloned();
+                let planned: Option<TracedTerm> = terms
+                    .iter()
+                    .find(|x| x.has_course(&course_name))
+                    .map(|x| TracedTerm {
+                        term: x.term_type.clone(),
+                        year: x.year,
+                    });
+
+                let slack: Option<usize> = match (&earliest, &planned) {
+                    (Some(x), Some(y)) => Some(
+                        get_term_offset(&options.start_term, &y.term, y.year)
+                            .saturating_sub(get_term_offset(&options.start_term, &x.term, x.year)),
+                    ),
+                    _ => None,
+                };
+
+                CourseSlack {
+                    course: course_name,
+                    earliest,
+                    planned,
+                    slack,
+                }
+            })
+            .collect();
+
+        course_slacks.sort_by_key(|x| x.slack.unwrap_or(usize::MAX));
+        course_slacks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+
+    fn traced_term(term: TermType, year: u16) -> TracedTerm {
+        TracedTerm { term, year }
+    }
+
+    #[test]
+    fn test_get_term_offset() {
+        assert_eq!(get_term_offset(&TermType::Fall, &TermType::Fall, 1), 0);
+        assert_eq!(get_term_offset(&TermType::Fall, &TermType::Spring, 2), 6);
+        assert_eq!(get_term_offset(&TermType::Spring, &TermType::Summer, 1), 1);
+        assert_eq!(get_term_offset(&TermType::Spring, &TermType::Winter, 2), 3);
+    }
+
+    #[test]
+    fn test_get_earliest_terms() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("CS 201L"), 2));
+        courses.add_course(Course::new(String::from("CS 490"), 4));
+        courses.add_course(Course::new(String::from("CS 491"), 4));
+        courses.add_course(Course::new(String::from("CS 999"), 20));
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_prerequisite(&String::from("CS 490"), &String::from("CS 201"));
+        courses.add_sequence(&[String::from("CS 490"), String::from("CS 491")]);
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options.start_at(&TermType::Spring);
+
+        let earliest_terms: HashMap<String, TracedTerm> = courses.get_earliest_terms(&options);
+        assert_eq!(earliest_terms["CS 101"], traced_term(TermType::Fall, 2));
+        assert_eq!(earliest_terms["CS 201"], traced_term(TermType::Winter, 2));
+        assert_eq!(earliest_terms["CS 201L"], traced_term(TermType::Winter, 2));
+        assert_eq!(earliest_terms["CS 490"], traced_term(TermType::Spring, 2));
+        assert_eq!(earliest_terms["CS 491"], traced_term(TermType::Summer, 2));
+        assert!(!earliest_terms.contains_key("CS 999"));
+
+        options.complete("CS 101");
+        let earliest_terms: HashMap<String, TracedTerm> = courses.get_earliest_terms(&options);
+        assert_eq!(earliest_terms["CS 201"], traced_term(TermType::Spring, 1));
+        assert!(!earliest_terms.contains_key("CS 101"));
+    }
+
+    #[test]
+    fn test_get_slack() {
+        let mut courses: Courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 101"), 4));
+        courses.add_course(Course::new(String::from("CS 201"), 4));
+        courses.add_course(Course::new(String::from("ART 1"), 4));
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+
+        let options = ScheduleOptions::new([4, 4, 4, 4]);
+        let mut terms: Vec<Term> = Vec::new();
+        for (term_type, course_name) in [
+            (TermType::Fall, "CS 101"),
+            (TermType::Winter, "CS 201"),
+            (TermType::Spring, "ART 1"),
+        ] {
+            let mut term = Term::new(&term_type, 4);
+            term.add(courses.get_course(course_name).unwrap()).unwrap();
+            terms.push(term);
+        }
+
+        let course_slacks: Vec<CourseSlack> = courses.get_slack(&terms, &options);
+        assert_eq!(
+            course_slacks
+                .iter()
+                .map(|x| (x.course.as_str(), x.slack))
+                .collect::<Vec<(&str, Option<usize>)>>(),
+            vec![("CS 101", Some(0)), ("CS 201", Some(0)), ("ART 1", Some(2))]
+        );
+        assert!(course_slacks[0].is_critical());
+        assert_eq!(
+            course_slacks[2].planned,
+            Some(traced_term(TermType::Spring, 1))
+        );
+    }
+}
@@ -0,0 +1,417 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{Courses, Credits, PendingPrerequisites, Term, TermType};
+
+/// Why the scheduler passed over a course in a term after its prerequisites
+/// were met.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SkipReason {
+    NotOffered,
+    /// The term did not have enough units left for the course.
+    UnitLimit,
+    /// The course and its concurrent courses, which are taken together, did
+    /// not fit in the units the term had left.
+    ConcurrentsTooLarge {
+        units: Credits,
+    },
+    /// The rest of the sequence the course starts did not fit in the terms
+    /// that follow, or the sequence had not started yet.
+    SequenceDoesNotFit,
+    /// The course was held back for its preferred term or until its
+    /// recommended courses were taken.
+    SoftPreference,
+    /// The course, or one equivalent to it, is pinned to another term.
+    Pinned,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::NotOffered => write!(f, "not offered"),
+            SkipReason::UnitLimit => write!(f, "not enough units left in the term"),
+            SkipReason::ConcurrentsTooLarge { units } => write!(
+                f,
+                "its concurrent courses need {} units together, more than the term had left",
+                units
+            ),
+            SkipReason::SequenceDoesNotFit => {
+                write!(f, "its sequence does not fit in the terms that follow")
+            }
+            SkipReason::SoftPreference => {
+                write!(f, "held back for its preferred term or recommended courses")
+            }
+            SkipReason::Pinned => write!(f, "pinned to another term"),
+        }
+    }
+}
+
+/// A term by its type and the year of the plan it falls in. Terms without
+/// any courses are left out of plans, so it is not always in the plan.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TracedTerm {
+    pub term: TermType,
+    pub year: u16,
+}
+
+impl fmt::Display for TracedTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, year {}", self.term, self.year)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TermSkip {
+    pub term: TracedTerm,
+    pub reason: SkipReason,
+}
+
+/// The decisions the scheduler made about one course.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CourseTrace {
+    /// The first term in which the prerequisites of the course were met.
+    pub eligible: Option<TracedTerm>,
+    /// The prerequisite that was completed last, meeting the prerequisites.
+    pub last_prerequisite: Option<String>,
+    /// The terms after becoming eligible that did not take the course.
+    pub skips: Vec<TermSkip>,
+    /// The first term taking the course.
+    pub planned: Option<TracedTerm>,
+}
+
+impl fmt::Display for CourseTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.eligible, &self.last_prerequisite) {
+            (Some(eligible), Some(prerequisite)) => writeln!(
+                f,
+                "Eligible from {}, once {} was completed",
+                eligible, prerequisite
+            )?,
+            (Some(eligible), None) => writeln!(f, "Eligible from {}", eligible)?,
+            (None, _) => writeln!(f, "Never eligible, as its prerequisites were never met")?,
+        }
+
+        for skip in &self.skips {
+            writeln!(f, "Skipped in {}: {}", skip.term, skip.reason)?;
+        }
+
+        match &self.planned {
+            Some(planned) => writeln!(f, "Planned for {}", planned),
+            None => writeln!(f, "Not planned"),
+        }
+    }
+}
+
+/// The decisions the scheduler made about every course it planned, recorded
+/// when `ScheduleOptions::record_trace` is set.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduleTrace {
+    courses: HashMap<String, CourseTrace>,
+    /// The number of terms gone through when each course counted as taken,
+    /// where 0 means before the first term.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    completed_at: HashMap<String, usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    terms_count: usize,
+}
+
+impl ScheduleTrace {
+    pub(crate) fn new(completed_courses: &HashSet<String>) -> ScheduleTrace {
+        ScheduleTrace {
+            courses: HashMap::new(),
+            completed_at: completed_courses.iter().map(|x| (x.clone(), 0)).collect(),
+            terms_count: 0,
+        }
+    }
+
+    pub fn get(&self, course: &str) -> Option<&CourseTrace> {
+        self.courses.get(course)
+    }
+
+    /// Gets the names of the traced courses, sorted.
+    pub fn course_names(&self) -> Vec<String> {
+        let mut course_names: Vec<String> = self.courses.keys().cloned().collect();
+        course_names.sort();
+
+        course_names
+    }
+
+    /// Finds the prerequisite of the course that was completed last, taking
+    /// the first by name when several were completed in the same term.
+    fn last_prerequisite(&self, courses: &Courses, course: &String) -> Option<String> {
+        let mut prerequisites: Vec<String> = courses
+            .get_prerequisites(course)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        prerequisites.extend(
+            courses
+                .get_prerequisite_choices(course)
+                .unwrap_or_default()
+                .into_iter()
+                .flatten(),
+        );
+        prerequisites.sort();
+
+        prerequisites
+            .into_iter()
+            .filter_map(|x| self.completed_at.get(&x).map(|y| (*y, x)))
+            .fold(None, |last: Option<(usize, String)>, x| match &last {
+                Some(y) if y.0 >= x.0 => last,
+                _ => Some(x),
+            })
+            .map(|x| x.1)
+    }
+
+    /// Gets the trace of the course, starting it as eligible from the term
+    /// when it has not been traced yet.
+    fn start_trace(
+        &mut self,
+        courses: &Courses,
+        course_name: &String,
+        term: &TracedTerm,
+    ) -> &mut CourseTrace {
+        if !self.courses.contains_key(course_name) {
+            let course_trace = CourseTrace {
+                eligible: Some(term.clone()),
+                last_prerequisite: self.last_prerequisite(courses, course_name),
+                ..CourseTrace::default()
+            };
+            self.courses.insert(course_name.clone(), course_trace);
+        }
+
+        self.courses.get_mut(course_name).unwrap()
+    }
+
+    /// Records the decisions made about every course that was not processed
+    /// before the term, whose prerequisites are given as they were during the
+    /// term. `skip_reasons` holds why the scheduler passed over
+    /// courses it considered, while courses it never got to were either not
+    /// offered or did not fit once the term filled up.
+    pub(crate) fn record_term(
+        &mut self,
+        courses: &Courses,
+        term: &Term,
+        prerequisites: &PendingPrerequisites,
+        processed_before: &HashSet<String>,
+        processed_after: &HashSet<String>,
+        skip_reasons: &HashMap<String, SkipReason>,
+    ) {
+        self.terms_count += 1;
+        let traced_term = TracedTerm {
+            term: term.term_type.clone(),
+            year: term.year,
+        };
+
+        for course_name in courses.master_list.keys() {
+            if processed_before.contains(course_name) || prerequisites.blocks(course_name) {
+                continue;
+            }
+
+            let course_trace: &mut CourseTrace =
+                self.start_trace(courses, course_name, &traced_term);
+
+            // Courses processed without being in the term were planned into
+            // the following terms as part of a sequence, or an equivalent was
+            // planned instead.
+            if term.has_course(course_name) || processed_after.contains(course_name) {
+                continue;
+            }
+
+            let reason: SkipReason = match skip_reasons.get(course_name) {
+                Some(x) => x.clone(),
+                None if !courses.master_list[course_name].is_available(&term.term_type) => {
+                    SkipReason::NotOffered
+                }
+                None if courses.get_sequence_for(course_name).is_some() => {
+                    SkipReason::SequenceDoesNotFit
+                }
+                None => SkipReason::UnitLimit,
+            };
+
+            course_trace.skips.push(TermSkip {
+                term: traced_term.clone(),
+                reason,
+            });
+        }
+    }
+
+    /// Records the courses the term takes, including those planned into it
+    /// ahead of time as part of a sequence, along with the courses that now
+    /// count as taken.
+    pub(crate) fn complete_term(
+        &mut self,
+        courses: &Courses,
+        term: &Term,
+        satisfied_courses: &HashSet<String>,
+    ) {
+        let traced_term = TracedTerm {
+            term: term.term_type.clone(),
+            year: term.year,
+        };
+
+        for (course_name, _) in &term.courses {
+            // Later courses of a sequence are planned ahead of time, so they
+            // only become eligible in the term they are planned for.
+            let course_trace: &mut CourseTrace =
+                self.start_trace(courses, course_name, &traced_term);
+            if course_trace.planned.is_none() {
+                course_trace.planned = Some(traced_term.clone());
+            }
+        }
+
+        for course_name in satisfied_courses {
+            self.completed_at
+                .entry(course_name.clone())
+                .or_insert(self.terms_count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Course, ScheduleOptions};
+
+    fn traced_term(term: TermType, year: u16) -> TracedTerm {
+        TracedTerm { term, year }
+    }
+
+    #[test]
+    fn test_trace_prerequisites() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+
+        let mut second_course: Course = Course::new(String::from("CS 201"), 4);
+        second_course.available_by(&TermType::Spring);
+        courses.add_course(second_course);
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        assert_eq!(courses.get_plan_with(&options).unwrap().trace(), None);
+
+        options.record_trace();
+        let plan = courses.get_plan_with(&options).unwrap();
+        let trace: &ScheduleTrace = plan.trace().unwrap();
+        assert_eq!(trace.course_names(), vec!["CS 101", "CS 201"]);
+
+        assert_eq!(
+            trace.get("CS 101"),
+            Some(&CourseTrace {
+                eligible: Some(traced_term(TermType::Fall, 1)),
+                last_prerequisite: None,
+                skips: Vec::new(),
+                planned: Some(traced_term(TermType::Fall, 1)),
+            })
+        );
+
+        let course_trace: &CourseTrace = trace.get("CS 201").unwrap();
+        assert_eq!(
+            course_trace,
+            &CourseTrace {
+                eligible: Some(traced_term(TermType::Winter, 1)),
+                last_prerequisite: Some(String::from("CS 101")),
+                skips: vec![TermSkip {
+                    term: traced_term(TermType::Winter, 1),
+                    reason: SkipReason::NotOffered,
+                }],
+                planned: Some(traced_term(TermType::Spring, 1)),
+            }
+        );
+        assert_eq!(
+            course_trace.to_string(),
+            "Eligible from Winter, year 1, once CS 101 was completed
+Skipped in Winter, year 1: not offered
+Planned for Spring, year 1
+"
+        );
+    }
+
+    #[test]
+    fn test_trace_unit_limits() {
+        let mut courses: Courses = Courses::new();
+        courses.add_course(Course::new(String::from("LAB 1"), 4));
+        courses.add_course(Course::new(String::from("LAB 1L"), 4));
+        courses
+            .add_concurrency(&String::from("LAB 1"), &String::from("LAB 1L"))
+            .unwrap();
+
+        let mut large_course: Course = Course::new(String::from("ART 1"), 7);
+        large_course
+            .available_by(&TermType::Fall)
+            .available_by(&TermType::Summer);
+        courses.add_course(large_course);
+
+        let mut options = ScheduleOptions::new([6, 6, 8, 8]);
+        options.record_trace();
+
+        let plan = courses.get_plan_with(&options).unwrap();
+        let trace: &ScheduleTrace = plan.trace().unwrap();
+
+        let too_large = SkipReason::ConcurrentsTooLarge {
+            units: Credits::from(8),
+        };
+        assert_eq!(
+            trace.get("LAB 1L").unwrap().skips,
+            vec![
+                TermSkip {
+                    term: traced_term(TermType::Fall, 1),
+                    reason: too_large.clone(),
+                },
+                TermSkip {
+                    term: traced_term(TermType::Winter, 1),
+                    reason: too_large,
+                },
+            ]
+        );
+
+        let course_trace: &CourseTrace = trace.get("ART 1").unwrap();
+        assert_eq!(
+            course_trace
+                .skips
+                .iter()
+                .map(|x| x.reason.clone())
+                .collect::<Vec<SkipReason>>(),
+            vec![
+                SkipReason::UnitLimit,
+                SkipReason::NotOffered,
+                SkipReason::NotOffered
+            ]
+        );
+        assert_eq!(course_trace.planned, Some(traced_term(TermType::Summer, 1)));
+    }
+
+    #[test]
+    fn test_trace_pinned_course() {
+        let mut courses: Courses = Courses::new();
+        courses.add_course(Course::new(String::from("CS 101"), 4));
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options.pin("CS 101", &TermType::Spring, 1).record_trace();
+
+        let plan = courses.get_plan_with(&options).unwrap();
+        let course_trace: &CourseTrace = plan.trace().unwrap().get("CS 101").unwrap();
+        assert_eq!(
+            course_trace.skips,
+            vec![
+                TermSkip {
+                    term: traced_term(TermType::Fall, 1),
+                    reason: SkipReason::Pinned,
+                },
+                TermSkip {
+                    term: traced_term(TermType::Winter, 1),
+                    reason: SkipReason::Pinned,
+                },
+            ]
+        );
+        assert_eq!(course_trace.planned, Some(traced_term(TermType::Spring, 1)));
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+use crate::{Courses, Credits, ScheduleOptions, Term, TermType};
+
+/// A rule of the catalog that a plan breaks, the rules `Courses::get_terms`
+/// follows when it schedules. Terms are indexes into the plan's terms.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Violation {
+    UnknownCourse {
+        course: String,
+        term: usize,
+    },
+    /// Neither the course nor one of its equivalents is planned or
+    /// completed.
+    CourseNotPlanned {
+        course: String,
+    },
+    PrerequisiteNotMet {
+        course: String,
+        term: usize,
+        prerequisite: String,
+    },
+    PrerequisiteChoiceNotMet {
+        course: String,
+        term: usize,
+        options: Vec<String>,
+    },
+    /// Only some courses of a concurrency group are in the term, and the
+    /// rest are elsewhere or not planned.
+    ConcurrentsSplit {
+        courses: Vec<String>,
+        term: usize,
+        missing: Vec<String>,
+    },
+    /// A course of a sequence is not planned in the term right after the
+    /// course before it.
+    SequenceBroken {
+        course: String,
+        term: usize,
+        previous: String,
+        previous_term: usize,
+    },
+    NotOffered {
+        course: String,
+        term: usize,
+        term_type: TermType,
+    },
+    UnitLimitExceeded {
+        term: usize,
+        units: Credits,
+        unit_limit: Credits,
+    },
+    /// An instance of the course is taken for fewer or more units than the
+    /// course allows.
+    CreditsOutOfRange {
+        course: String,
+        term: usize,
+        units: Credits,
+    },
+    RepeatLimitExceeded {
+        course: String,
+        instances: usize,
+        repeat_limit: u8,
+    },
+    TargetCreditsNotMet {
+        course: String,
+        credits: Credits,
+        target_credits: Credits,
+    },
+}
+
+impl Violation {
+    /// Gets the courses that break the rule, which is none for a term over
+    /// its unit limit.
+    pub fn courses(&self) -> Vec<&str> {
+        match self {
+            Violation::UnitLimitExceeded { .. } => Vec::new(),
+            Violation::ConcurrentsSplit { courses, .. } => {
+                courses.iter().map(|x| x.as_str()).collect()
+            }
+            Violation::UnknownCourse { course, .. }
+            | Violation::CourseNotPlanned { course }
+            | Violation::PrerequisiteNotMet { course, .. }
+            | Violation::PrerequisiteChoiceNotMet { course, .. }
+            | Violation::SequenceBroken { course, .. }
+            | Violation::NotOffered { course, .. }
+            | Violation::CreditsOutOfRange { course, .. }
+            | Violation::RepeatLimitExceeded { course, .. }
+            | Violation::TargetCreditsNotMet { course, .. } => vec![course],
+        }
+    }
+
+    /// Gets the term breaking the rule, unless the rule is about the plan as
+    /// a whole.
+    pub fn term(&self) -> Option<usize> {
+        match self {
+            Violation::UnknownCourse { term, .. }
+            | Violation::PrerequisiteNotMet { term, .. }
+            | Violation::PrerequisiteChoiceNotMet { term, .. }
+            | Violation::ConcurrentsSplit { term, .. }
+            | Violation::SequenceBroken { term, .. }
+            | Violation::NotOffered { term, .. }
+            | Violation::UnitLimitExceeded { term, .. }
+            | Violation::CreditsOutOfRange { term, .. } => Some(*term),
+            Violation::CourseNotPlanned { .. }
+            | Violation::RepeatLimitExceeded { .. }
+            | Violation::TargetCreditsNotMet { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::UnknownCourse { course, term } => {
+                write!(f, "{} in term {} is not in the catalog", course, term + 1)
+            }
+            Violation::CourseNotPlanned { course } => write!(f, "{} is not planned", course),
+            Violation::PrerequisiteNotMet {
+                course,
+                term,
+                prerequisite,
+            } => write!(
+                f,
+                "{} in term {} needs {} in an earlier term",
+                course,
+                term + 1,
+                prerequisite
+            ),
+            Violation::PrerequisiteChoiceNotMet {
+                course,
+                term,
+                options,
+            } => write!(
+                f,
+                "{} in term {} needs one of {} in an earlier term",
+                course,
+                term + 1,
+                options.join(" or ")
+            ),
+            Violation::ConcurrentsSplit {
+                courses,
+                term,
+                missing,
+            } => write!(
+                f,
+                "{} in term {} must be taken in the same term as {}",
+                courses.join(", "),
+                term + 1,
+                missing.join(", ")
+            ),
+            Violation::SequenceBroken {
+                course,
+                term,
+                previous,
+                previous_term,
+            } => write!(
+                f,
+                "{} in term {} must be taken in the term right after {}, which is in term {}",
+                course,
+                term + 1,
+                previous,
+                previous_term + 1
+            ),
+            Violation::NotOffered {
+                course,
+                term,
+                term_type,
+            } => write!(
+                f,
+                "{} in term {} is not offered in {}",
+                course,
+                term + 1,
+                term_type
+            ),
+            Violation::UnitLimitExceeded {
+                term,
+                units,
+                unit_limit,
+            } => write!(
+                f,
+                "Term {} has {} units, over its limit of {}",
+                term + 1,
+                units,
+                unit_limit
+            ),
+            Violation::CreditsOutOfRange {
+                course,
+                term,
+                units,
+            } => write!(
+                f,
+                "{} in term {} is taken for {} units, which it cannot be taken for",
+                course,
+                term + 1,
+                units
+            ),
+            Violation::RepeatLimitExceeded {
+                course,
+                instances,
+                repeat_limit,
+            } => write!(
+                f,
+                "{} is planned {} times, but can only be taken {} times",
+                course, instances, repeat_limit
+            ),
+            Violation::TargetCreditsNotMet {
+                course,
+                credits,
+                target_credits,
+            } => write!(
+                f,
+                "{} is planned for {} units, short of its {} units",
+                course, credits, target_credits
+            ),
+        }
+    }
+}
+
+/// Adds the course and its equivalents to the courses that count as taken.
+fn complete(courses: &Courses, completed_courses: &mut HashSet<String>, course: &String) {
+    completed_courses.insert(course.clone());
+    completed_courses.extend(courses.get_equivalents_for(course).unwrap_or_default());
+}
+
+/// Checks the terms against every rule of the catalog, listing the rules
+/// broken term by term, followed by those about the plan as a whole.
+pub(crate) fn validate(
+    courses: &Courses,
+    terms: &[Term],
+    options: &ScheduleOptions,
+) -> Vec<Violation> {
+    let mut violations: Vec<Violation> = Vec::new();
+    let mut completed_courses: HashSet<String> = HashSet::new();
+
+    for course_name in &options.completed_courses {
+        complete(courses, &mut completed_courses, course_name);
+    }
+    let completed_before_plan: HashSet<String> = completed_courses.clone();
+
+    for (index, term) in terms.iter().enumerate() {
+        let mut checked_groups: BTreeSet<Vec<String>> = BTreeSet::new();
+
+        for (course_name, units) in &term.courses {
+            let course = match courses.master_list.get(course_name) {
+                Some(x) => x,
+                None => {
+                    violations.push(Violation::UnknownCourse {
+                        course: course_name.clone(),
+                        term: index,
+                    });
+                    continue;
+                }
+            };
+
+            let mut prerequisites: Vec<String> = courses
+                .get_prerequisites(course_name)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|x| !completed_courses.contains(x))
+                .collect();
+            prerequisites.sort();
+
+            violations.extend(
+                prerequisites
+                    .into_iter()
+                    .map(|x| Violation::PrerequisiteNotMet {
+                        course: course_name.clone(),
+                        term: index,
+                        prerequisite: x,
+                    }),
+            );
+
+            for choice in courses
+                .get_prerequisite_choices(course_name)
+                .unwrap_or_default()
+            {
+                if !choice.iter().any(|x| completed_courses.contains(x)) {
+                    violations.push(Violation::PrerequisiteChoiceNotMet {
+                        course: course_name.clone(),
+                        term: index,
+                        options: choice,
+                    });
+                }
+            }
+
+            if let Some((concurrents, _)) = courses.get_concurrents_for(course_name) {
+                // Courses completed before the plan do not have to be taken
+                // again alongside the rest of their group.
+                let mut group: Vec<String> = concurrents
+                    .into_iter()
+                    .filter(|x| !completed_before_plan.contains(x))
+                    .collect();
+                group.sort();
+
+                if !group.iter().all(|x| term.has_course(x)) && checked_groups.insert(group.clone())
+                {
+                    let (present_courses, missing_courses): (Vec<String>, Vec<String>) =
+                        group.into_iter().partition(|x| term.has_course(x));
+
+                    violations.push(Violation::ConcurrentsSplit {
+                        courses: present_courses,
+                        term: index,
+                        missing: missing_courses,
+                    });
+                }
+            }
+
+            if let Some(sequence) = courses.get_sequence_for(course_name) {
+                let position: usize = sequence.iter().position(|x| x == course_name).unwrap();
+
+                if position > 0 {
+                    let previous: &String = &sequence[position - 1];
+                    let previous_term: Option<usize> =
+                        terms.iter().position(|x| x.has_course(previous));
+
+                    if let Some(previous_term) = previous_term.filter(|x| x + 1 != index) {
+                        violations.push(Violation::SequenceBroken {
+                            course: course_name.clone(),
+                            term: index,
+                            previous: previous.clone(),
+                            previous_term,
+                        });
+                    }
+                }
+            }
+
+            if !course.is_available(&term.term_type) {
+                violations.push(Violation::NotOffered {
+                    course: course_name.clone(),
+                    term: index,
+                    term_type: term.term_type.clone(),
+                });
+            }
+
+            if *units < course.credits || *units > course.max_credits {
+                violations.push(Violation::CreditsOutOfRange {
+                    course: course_name.clone(),
+                    term: index,
+                    units: *units,
+                });
+            }
+        }
+
+        if term.units > term.unit_limit {
+            violations.push(Violation::UnitLimitExceeded {
+                term: index,
+                units: term.units,
+                unit_limit: term.unit_limit,
+            });
+        }
+
+        for (course_name, _) in &term.courses {
+            complete(courses, &mut completed_courses, course_name);
+        }
+    }
+
+    for course_name in courses.get_course_names() {
+        let course = &courses.master_list[&course_name];
+
+        if !completed_courses.contains(&course_name) {
+            violations.push(Violation::CourseNotPlanned {
+                course: course_name,
+            });
+            continue;
+        }
+
+        let instances: Vec<Credits> = terms
+            .iter()
+            .flat_map(|x| &x.courses)
+            .filter(|x| x.0 == course_name)
+            .map(|x| x.1)
+            .collect();
+        if instances.is_empty() {
+            continue;
+        }
+
+        if instances.len() > course.repeat_limit as usize {
+            violations.push(Violation::RepeatLimitExceeded {
+                course: course_name.clone(),
+                instances: instances.len(),
+                repeat_limit: course.repeat_limit,
+            });
+        }
+
+        let credits: Credits = Credits::checked_sum(instances).unwrap_or(Credits::MAX);
+        if credits < course.target_credits {
+            violations.push(Violation::TargetCreditsNotMet {
+                course: course_name,
+                credits,
+                target_credits: course.target_credits,
+            });
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Course;
+
+    fn get_term(term_type: &TermType, courses: &Courses, course_names: &[&str]) -> Term {
+        let mut term = Term::new(term_type, 8);
+
+        for course_name in course_names {
+            term.add(courses.get_course(course_name).unwrap()).unwrap();
+        }
+
+        term
+    }
+
+    #[test]
+    fn test_validate_scheduled_terms() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+
+        for course_name in ["CS 201", "CS 201L", "CS 301", "CS 302"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_sequence(&[String::from("CS 301"), String::from("CS 302")]);
+
+        let terms: Vec<Term> = courses.get_terms([8, 8, 8, 8]).unwrap();
+
+        assert_eq!(courses.validate_terms(&terms), Vec::new());
+    }
+
+    #[test]
+    fn test_validate_terms() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+
+        for course_name in ["CS 201", "CS 201L", "CS 301", "CS 302"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_sequence(&[String::from("CS 301"), String::from("CS 302")]);
+
+        let terms: Vec<Term> = vec![
+            get_term(&TermType::Fall, &courses, &["CS 201", "CS 301"]),
+            get_term(&TermType::Winter, &courses, &["CS 101", "CS 201L"]),
+            get_term(&TermType::Spring, &courses, &["CS 302"]),
+        ];
+
+        let violations: Vec<Violation> = courses.validate_terms(&terms);
+        assert_eq!(
+            violations,
+            vec![
+                Violation::PrerequisiteNotMet {
+                    course: String::from("CS 201"),
+                    term: 0,
+                    prerequisite: String::from("CS 101"),
+                },
+                Violation::ConcurrentsSplit {
+                    courses: vec![String::from("CS 201")],
+                    term: 0,
+                    missing: vec![String::from("CS 201L")],
+                },
+                Violation::NotOffered {
+                    course: String::from("CS 101"),
+                    term: 1,
+                    term_type: TermType::Winter,
+                },
+                Violation::PrerequisiteNotMet {
+                    course: String::from("CS 201L"),
+                    term: 1,
+                    prerequisite: String::from("CS 101"),
+                },
+                Violation::ConcurrentsSplit {
+                    courses: vec![String::from("CS 201L")],
+                    term: 1,
+                    missing: vec![String::from("CS 201")],
+                },
+                Violation::SequenceBroken {
+                    course: String::from("CS 302"),
+                    term: 2,
+                    previous: String::from("CS 301"),
+                    previous_term: 0,
+                },
+            ]
+        );
+        assert_eq!(violations[5].courses(), vec!["CS 302"]);
+        assert_eq!(
+            violations[5].to_string(),
+            "CS 302 in term 3 must be taken in the term right after CS 301, which is in term 1"
+        );
+    }
+
+    #[test]
+    fn test_validate_terms_with_completed_courses() {
+        let mut courses: Courses = Courses::new();
+
+        let mut first_course: Course = Course::new(String::from("CS 101"), 4);
+        first_course.available_by(&TermType::Fall);
+        courses.add_course(first_course);
+
+        for course_name in ["CS 201", "CS 201L", "CS 301", "CS 302"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+
+        courses.add_prerequisite(&String::from("CS 201"), &String::from("CS 101"));
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses.add_sequence(&[String::from("CS 301"), String::from("CS 302")]);
+
+        let mut repeated_course: Course = Course::new(String::from("CS 199"), 2);
+        repeated_course.repeatable(2).set_target_credits(6);
+        courses.add_course(repeated_course);
+
+        let mut terms: Vec<Term> = vec![get_term(
+            &TermType::Fall,
+            &courses,
+            &["CS 201", "CS 201L", "CS 199"],
+        )];
+        terms[0].add(courses.get_course("CS 199").unwrap()).unwrap();
+
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options.complete("CS 101").complete("CS 301");
+
+        assert_eq!(
+            courses.validate_terms_with(&terms, &options),
+            vec![
+                Violation::UnitLimitExceeded {
+                    term: 0,
+                    units: Credits::from(12),
+                    unit_limit: Credits::from(8),
+                },
+                Violation::TargetCreditsNotMet {
+                    course: String::from("CS 199"),
+                    credits: Credits::from(4),
+                    target_credits: Credits::from(6),
+                },
+                Violation::CourseNotPlanned {
+                    course: String::from("CS 302"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_terms_with_completed_concurrent() {
+        let mut courses: Courses = Courses::new();
+        for course_name in ["CS 201", "CS 201L", "CS 201P"] {
+            courses.add_course(Course::new(course_name.to_string(), 4));
+        }
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201L"))
+            .unwrap();
+        courses
+            .add_concurrency(&String::from("CS 201"), &String::from("CS 201P"))
+            .unwrap();
+
+        let terms: Vec<Term> = vec![get_term(&TermType::Fall, &courses, &["CS 201"])];
+        let mut options = ScheduleOptions::new([8, 8, 8, 8]);
+        options.complete("CS 201L");
+
+        assert_eq!(
+            courses.validate_terms_with(&terms, &options),
+            vec![
+                Violation::ConcurrentsSplit {
+                    courses: vec![String::from("CS 201")],
+                    term: 0,
+                    missing: vec![String::from("CS 201P")],
+                },
+                Violation::CourseNotPlanned {
+                    course: String::from("CS 201P"),
+                }
+            ]
+        );
+
+        options.complete("CS 201P");
+        assert_eq!(courses.validate_terms_with(&terms, &options), Vec::new());
+    }
+}